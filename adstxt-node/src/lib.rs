@@ -0,0 +1,223 @@
+//! Node.js bindings for the [`adstxt`] parser.
+//!
+//! Exposes `parse`, `validate` and `diff` to JavaScript with the parsing semantics of the Rust crate. Field names
+//! are converted to camelCase, and spans are reported as UTF-16 offsets, so that they can be passed to
+//! `String.prototype.slice`, along with 1-based line and column numbers.
+
+use std::ops::Range;
+
+use adstxt::{
+    validate::{self, Limits},
+    AdsTxt,
+};
+use napi::{Error, Result};
+use napi_derive::napi;
+
+/// Record of ads.txt.
+#[napi(object)]
+pub struct Record {
+    pub domain: String,
+    pub account_id: String,
+    pub relation: String,
+    pub authority_id: Option<String>,
+    pub extension: Option<String>,
+}
+
+/// Variable record of ads.txt.
+#[napi(object)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub extension: Option<String>,
+}
+
+/// Records and variables of ads.txt.
+#[napi(object)]
+pub struct ParseResult {
+    pub records: Vec<Record>,
+    pub variables: Vec<Variable>,
+}
+
+/// Problem found in ads.txt.
+#[napi(object)]
+pub struct Diagnostic {
+    /// `"error"` or `"warning"`.
+    pub severity: String,
+    pub message: String,
+    pub hint: Option<String>,
+    /// UTF-16 offset of the start of the span.
+    pub start: u32,
+    /// UTF-16 offset of the end of the span.
+    pub end: u32,
+    pub line: u32,
+    /// Counted in UTF-16 code units.
+    pub column: u32,
+    /// rustc-style rendering of the diagnostic with its source line.
+    pub rendered: String,
+}
+
+/// Order-insensitive difference between two ads.txt files.
+#[napi(object)]
+pub struct Diff {
+    pub records_only_in_left: Vec<Record>,
+    pub records_only_in_right: Vec<Record>,
+    pub variables_only_in_left: Vec<Variable>,
+    pub variables_only_in_right: Vec<Variable>,
+}
+
+/// Parses ads.txt.
+#[napi]
+pub fn parse(text: String) -> ParseResult {
+    let txt = AdsTxt::parse(&text);
+    ParseResult {
+        records: txt.records.iter().map(|(r, e)| record(r, e.as_ref().map(|x| x.0))).collect(),
+        variables: txt.variables.iter().map(|(v, e)| variable(v, e.as_ref().map(|x| x.0))).collect(),
+    }
+}
+
+/// Reports syntax problems and the findings of the rules that need no external data: contacts, duplicate
+/// variables and the default size limits.
+///
+/// Fails if an offset does not fit in 32 bits.
+#[napi]
+pub fn validate(text: String) -> Result<Vec<Diagnostic>> {
+    let lines = AdsTxt::parse_spanned(&text);
+    let mut diagnostics = AdsTxt::diagnose(&text);
+    diagnostics.extend(validate::contacts(&lines));
+    diagnostics.extend(validate::duplicate_variables(&lines));
+    diagnostics.extend(validate::limits(&lines, &Limits::default()));
+    diagnostics.sort_by_key(|x| x.span.start);
+    let mut positions = Positions::new(&text);
+    diagnostics.iter().map(|x| diagnostic(x, &text, &mut positions)).collect()
+}
+
+/// Compares two ads.txt files ignoring the order of lines, duplicated lines and comments, as `AdsTxt::compare`.
+#[napi]
+pub fn diff(left: String, right: String) -> Diff {
+    let (left, right) = (AdsTxt::parse(&left), AdsTxt::parse(&right));
+    let comparison = left.compare(&right);
+    Diff {
+        records_only_in_left: comparison.records_only_in_self.into_iter().map(|x| record(x, None)).collect(),
+        records_only_in_right: comparison.records_only_in_other.into_iter().map(|x| record(x, None)).collect(),
+        variables_only_in_left: comparison.variables_only_in_self.into_iter().map(|x| variable(x, None)).collect(),
+        variables_only_in_right: comparison.variables_only_in_other.into_iter().map(|x| variable(x, None)).collect(),
+    }
+}
+
+fn record(record: &adstxt::Record<'_>, extension: Option<&str>) -> Record {
+    Record {
+        domain: record.domain.to_owned(),
+        account_id: record.account_id.to_owned(),
+        relation: record.relation.as_str().to_owned(),
+        authority_id: record.authority_id.map(ToOwned::to_owned),
+        extension: extension.map(ToOwned::to_owned),
+    }
+}
+
+fn variable(variable: &adstxt::Variable<'_>, extension: Option<&str>) -> Variable {
+    Variable {
+        name: variable.name.to_owned(),
+        value: variable.value.to_owned(),
+        extension: extension.map(ToOwned::to_owned),
+    }
+}
+
+fn diagnostic(diagnostic: &adstxt::Diagnostic, source: &str, positions: &mut Positions<'_>) -> Result<Diagnostic> {
+    let (start, line, column) = positions.seek(diagnostic.span.start)?;
+    let end = start + utf16_len(source, diagnostic.span.clone())?;
+    Ok(Diagnostic {
+        severity: diagnostic.severity.to_string(),
+        message: diagnostic.message.clone(),
+        hint: diagnostic.hint.clone(),
+        start: to_u32(start)?,
+        end: to_u32(end)?,
+        line: to_u32(line)?,
+        column: to_u32(column)?,
+        rendered: diagnostic.render(source),
+    })
+}
+
+/// Converts UTF-8 byte offsets of a source to UTF-16 offsets and line and column numbers, scanning the source
+/// once when the offsets are increasing.
+struct Positions<'s> {
+    source: &'s str,
+    offset: usize,
+    utf16: usize,
+    line: usize,
+    line_start: usize,
+}
+
+impl<'s> Positions<'s> {
+    fn new(source: &'s str) -> Self {
+        Positions { source, offset: 0, utf16: 0, line: 1, line_start: 0 }
+    }
+
+    /// Returns the UTF-16 offset, the 1-based line number and the 1-based UTF-16 column of `offset`.
+    fn seek(&mut self, offset: usize) -> Result<(usize, usize, usize)> {
+        if offset < self.offset {
+            *self = Positions::new(self.source);
+        }
+        for c in slice(self.source, self.offset..offset)?.chars() {
+            self.utf16 += c.len_utf16();
+            if c == '\n' {
+                self.line += 1;
+                self.line_start = self.utf16;
+            }
+        }
+        self.offset = offset;
+        Ok((self.utf16, self.line, self.utf16 - self.line_start + 1))
+    }
+}
+
+fn utf16_len(source: &str, span: Range<usize>) -> Result<usize> {
+    Ok(slice(source, span)?.encode_utf16().count())
+}
+
+fn slice(source: &str, span: Range<usize>) -> Result<&str> {
+    source.get(span.clone()).ok_or_else(|| Error::from_reason(format!("span {span:?} is not on character boundaries")))
+}
+
+fn to_u32(x: usize) -> Result<u32> {
+    u32::try_from(x).map_err(|_| Error::from_reason(format!("offset {x} does not fit in 32 bits")))
+}
+
+#[test]
+fn test_bindings() {
+    let result = parse("greenadexchange.com, 12345, DIRECT ; ext\ncontact=adops@example.com".to_owned());
+    assert_eq!(result.records[0].relation, "DIRECT");
+    assert_eq!(result.records[0].extension.as_deref(), Some("ext"));
+    assert_eq!(result.variables[0].value, "adops@example.com");
+
+    let diagnostics = validate("greenadexchange.com, 12345, DIRECT\nunknown".to_owned()).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].start, diagnostics[0].line, diagnostics[0].column), (35, 2, 1));
+    assert_eq!(diagnostics[0].severity, "warning");
+
+    let diff = diff("a.com, 1, DIRECT\nb.com, 2, DIRECT".to_owned(), "B.COM, 2, DIRECT\nname=value".to_owned());
+    assert_eq!(diff.records_only_in_left.iter().map(|x| x.domain.as_str()).collect::<Vec<_>>(), vec!["a.com"]);
+    assert!(diff.records_only_in_right.is_empty());
+    assert_eq!(diff.variables_only_in_right.len(), 1);
+}
+
+#[test]
+fn test_utf16_positions() {
+    // "ä" and "😀" are 2 and 4 bytes in UTF-8 but 1 and 2 code units in UTF-16.
+    let text = "# ä😀\nä😀.com, 1, DIRCT ; ä\nunknown";
+    let diagnostics = validate(text.to_owned()).unwrap();
+    let utf16: Vec<_> = text.encode_utf16().collect();
+    let spans: Vec<_> = diagnostics
+        .iter()
+        .map(|x| {
+            let slice = String::from_utf16(&utf16[x.start as usize..x.end as usize]).unwrap();
+            (slice, x.line, x.column)
+        })
+        .collect();
+    assert!(spans.contains(&("DIRCT".to_owned(), 2, 13)));
+    assert!(spans.contains(&("unknown".to_owned(), 3, 1)));
+
+    let mut positions = Positions::new(text);
+    assert_eq!(positions.seek(text.len()).unwrap(), (utf16.len(), 3, 8));
+    assert_eq!(positions.seek(9).unwrap(), (6, 2, 1));
+    assert!(positions.seek(3).is_err());
+    assert!(to_u32(usize::MAX).is_err());
+}
@@ -0,0 +1,429 @@
+//! Aggregate statistics over a corpus of ads.txt files.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    thread,
+};
+
+use crate::{canonical_domain, AdsTxt, Comparison, LineData, Record, Relation, Variable};
+
+/// Represents aggregate statistics over many ads.txt files.
+///
+/// Statistics are accumulated with [`CorpusStats::add`] and can be computed on several threads and combined
+/// with [`CorpusStats::merge`], which is what [`CorpusStats::from_parallel`] does.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::analysis::CorpusStats;
+///
+/// let stats: CorpusStats = [
+///     "greenadexchange.com, 12345, DIRECT\nOWNERDOMAIN=example.com",
+///     "greenadexchange.com, 678, RESELLER\nblueadexchange.com, XF436, RESELLER\nunknown",
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(stats.market_share()[0], ("greenadexchange.com", 1.0));
+/// assert_eq!(stats.reseller_density(), 2.0 / 3.0);
+/// assert_eq!(stats.variable_adoption("ownerdomain"), 0.5);
+/// assert_eq!(stats.parse_failure_rate(), 0.5);
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorpusStats {
+    /// Number of documents.
+    pub documents: usize,
+    /// Number of documents with at least one unrecognized line.
+    pub documents_with_unknown_lines: usize,
+    /// Number of lines.
+    pub lines: usize,
+    /// Number of unrecognized lines.
+    pub unknown_lines: usize,
+    /// Number of records.
+    pub records: usize,
+    /// Number of `DIRECT` records.
+    pub direct_records: usize,
+    /// Number of `RESELLER` records.
+    pub reseller_records: usize,
+    /// Number of documents listing each advertising system, keyed by its [`canonical_domain`].
+    pub exchanges: HashMap<String, usize>,
+    /// Number of documents declaring each lowercased variable name.
+    pub variables: HashMap<String, usize>,
+}
+
+impl CorpusStats {
+    /// Adds the statistics of one document.
+    pub fn add(&mut self, data: &str) {
+        let mut exchanges = Vec::new();
+        let mut variables = Vec::new();
+        let mut unknown_lines = 0;
+
+        for line in AdsTxt::parse_lines(data) {
+            self.lines += 1;
+            match line {
+                LineData::Record { record, .. } => {
+                    self.records += 1;
+                    match record.relation {
+                        Relation::Direct => self.direct_records += 1,
+                        Relation::Reseller => self.reseller_records += 1,
+                        Relation::Unknown(_) => {}
+                    }
+                    exchanges.push(canonical_domain(record.domain).into_owned());
+                }
+                LineData::Variable { variable, .. } => variables.push(variable.name.to_ascii_lowercase()),
+                LineData::Unknown(_) => unknown_lines += 1,
+                _ => {}
+            }
+        }
+
+        self.documents += 1;
+        self.unknown_lines += unknown_lines;
+        if unknown_lines > 0 {
+            self.documents_with_unknown_lines += 1;
+        }
+        count_distinct(&mut self.exchanges, exchanges);
+        count_distinct(&mut self.variables, variables);
+    }
+
+    /// Adds the statistics of `other`.
+    pub fn merge(&mut self, other: CorpusStats) {
+        self.documents += other.documents;
+        self.documents_with_unknown_lines += other.documents_with_unknown_lines;
+        self.lines += other.lines;
+        self.unknown_lines += other.unknown_lines;
+        self.records += other.records;
+        self.direct_records += other.direct_records;
+        self.reseller_records += other.reseller_records;
+        for (k, v) in other.exchanges {
+            *self.exchanges.entry(k).or_default() += v;
+        }
+        for (k, v) in other.variables {
+            *self.variables.entry(k).or_default() += v;
+        }
+    }
+
+    /// Computes the statistics of `documents` using all available CPUs.
+    pub fn from_parallel<S: AsRef<str> + Sync>(documents: &[S]) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |x| x.get());
+        let chunk_size = documents.len().div_ceil(threads).max(1);
+
+        thread::scope(|s| {
+            let handles: Vec<_> = documents
+                .chunks(chunk_size)
+                .map(|chunk| s.spawn(|| chunk.iter().map(|x| x.as_ref()).collect::<CorpusStats>()))
+                .collect();
+            handles.into_iter().fold(CorpusStats::default(), |mut acc, x| {
+                acc.merge(x.join().expect("analysis thread panicked"));
+                acc
+            })
+        })
+    }
+
+    /// Returns the share of documents listing each advertising system, in descending order of share then in
+    /// ascending order of domain.
+    pub fn market_share(&self) -> Vec<(&str, f64)> {
+        let mut share: Vec<_> = self.exchanges.iter().map(|(k, v)| (k.as_str(), ratio(*v, self.documents))).collect();
+        share.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        share
+    }
+
+    /// Returns the share of `RESELLER` records among all records.
+    pub fn reseller_density(&self) -> f64 {
+        ratio(self.reseller_records, self.records)
+    }
+
+    /// Returns the share of documents declaring the variable `name` (case-insensitive), e.g. `OWNERDOMAIN`,
+    /// `MANAGERDOMAIN` or `INVENTORYPARTNERDOMAIN` for the adoption of ads.txt 1.1.
+    pub fn variable_adoption(&self, name: &str) -> f64 {
+        ratio(self.variables.get(&name.to_ascii_lowercase()).copied().unwrap_or_default(), self.documents)
+    }
+
+    /// Returns the share of documents with at least one unrecognized line.
+    pub fn parse_failure_rate(&self) -> f64 {
+        ratio(self.documents_with_unknown_lines, self.documents)
+    }
+}
+
+impl<'a> FromIterator<&'a str> for CorpusStats {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        let mut stats = CorpusStats::default();
+        for x in iter {
+            stats.add(x);
+        }
+        stats
+    }
+}
+
+/// Represents the duplicates and conflicts found in the ads.txt of one publisher.
+///
+/// See also [`conflict_reports`].
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConflictReport<'a> {
+    /// Domain the ads.txt was fetched from.
+    pub publisher: &'a str,
+    /// Repeated occurrences of records already listed with the same [`RecordKey`](crate::RecordKey) and
+    /// certification authority ID.
+    pub duplicate_records: Vec<&'a Record<'a>>,
+    /// Pairs of `DIRECT` and `RESELLER` records for the same advertising system and account ID.
+    pub relation_conflicts: Vec<(&'a Record<'a>, &'a Record<'a>)>,
+    /// Variables contradicting each other: every `OWNERDOMAIN` if there are several distinct ones, and the
+    /// `MANAGERDOMAIN`s repeating a country.
+    pub conflicting_variables: Vec<&'a Variable<'a>>,
+}
+
+impl ConflictReport<'_> {
+    /// Returns `true` if nothing was found.
+    pub fn is_empty(&self) -> bool {
+        self.duplicate_records.is_empty() && self.relation_conflicts.is_empty() && self.conflicting_variables.is_empty()
+    }
+}
+
+/// Returns the duplicate and conflict report of every publisher with at least one finding.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{analysis::conflict_reports, AdsTxt};
+///
+/// let documents = [
+///     ("example.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT\ngreenadexchange.com, 12345, RESELLER")),
+///     ("example.org", AdsTxt::parse("greenadexchange.com, 12345, DIRECT")),
+/// ];
+///
+/// let reports = conflict_reports(&documents);
+/// assert_eq!(reports.len(), 1);
+/// assert_eq!(reports[0].publisher, "example.com");
+/// assert_eq!(reports[0].relation_conflicts.len(), 1);
+/// ```
+pub fn conflict_reports<'a>(documents: &'a [(&'a str, AdsTxt<'a>)]) -> Vec<ConflictReport<'a>> {
+    documents.iter().map(|(publisher, adstxt)| conflict_report(publisher, adstxt)).filter(|x| !x.is_empty()).collect()
+}
+
+fn conflict_report<'a>(publisher: &'a str, adstxt: &'a AdsTxt<'a>) -> ConflictReport<'a> {
+    let mut seen = HashSet::new();
+    let duplicate_records =
+        adstxt.records.iter().map(|(r, _)| r).filter(|r| !seen.insert((r.key(), r.authority_id))).collect();
+
+    let mut relations: HashMap<_, (Option<&Record>, Option<&Record>)> = HashMap::new();
+    let mut order = Vec::new();
+    for (r, _) in &adstxt.records {
+        let key = r.key();
+        let entry = relations.entry((key.domain, key.account_id)).or_insert_with_key(|k| {
+            order.push(k.clone());
+            (None, None)
+        });
+        match r.relation {
+            Relation::Direct => entry.0 = entry.0.or(Some(r)),
+            Relation::Reseller => entry.1 = entry.1.or(Some(r)),
+            Relation::Unknown(_) => {}
+        }
+    }
+    let relation_conflicts = order
+        .into_iter()
+        .filter_map(|k| match relations[&k] {
+            (Some(direct), Some(reseller)) => Some((direct, reseller)),
+            _ => None,
+        })
+        .collect();
+
+    let owner_domains: Vec<_> =
+        adstxt.variables.iter().map(|(v, _)| v).filter(|v| v.name.eq_ignore_ascii_case("ownerdomain")).collect();
+    let distinct: HashSet<_> = owner_domains.iter().map(|v| canonical_domain(v.value)).collect();
+    let mut conflicting_variables = if distinct.len() > 1 { owner_domains } else { Vec::new() };
+
+    let mut countries = HashSet::new();
+    conflicting_variables.extend(adstxt.variables.iter().map(|(v, _)| v).filter(|v| match v.manager_domain() {
+        Some(x) => !countries.insert(x.country.map(|c| c.to_ascii_uppercase())),
+        None => false,
+    }));
+
+    ConflictReport { publisher, duplicate_records, relation_conflicts, conflicting_variables }
+}
+
+/// Represents the change of one domain between two snapshots of a corpus.
+///
+/// See also [`changes`].
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value", rename_all = "snake_case"))]
+pub enum DomainChange<'a> {
+    /// The domain has an ads.txt only in the newer snapshot.
+    Appeared(&'a str),
+    /// The domain has an ads.txt only in the older snapshot.
+    Disappeared(&'a str),
+    /// The ads.txt of the domain changed. In the comparison, `*_only_in_self` are the removed entries and
+    /// `*_only_in_other` the added ones.
+    Changed(&'a str, Comparison<'a>),
+}
+
+/// Returns the per-domain changes between two snapshots of `(domain, ads.txt)` pairs, ordered by domain.
+///
+/// Domains are matched by their [`canonical_domain`] and files are compared with [`AdsTxt::compare`], so
+/// reordering lines or editing comments is not a change.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{analysis::*, AdsTxt};
+///
+/// let before = [
+///     ("a.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT")),
+///     ("b.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT")),
+/// ];
+/// let after = [
+///     ("a.com", AdsTxt::parse("# reordered\ngreenadexchange.com, 12345, DIRECT")),
+///     ("c.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT")),
+/// ];
+///
+/// assert_eq!(changes(&before, &after), vec![DomainChange::Disappeared("b.com"), DomainChange::Appeared("c.com")]);
+/// ```
+pub fn changes<'a>(before: &'a [(&'a str, AdsTxt<'a>)], after: &'a [(&'a str, AdsTxt<'a>)]) -> Vec<DomainChange<'a>> {
+    let mut domains: BTreeMap<_, (Option<_>, Option<_>)> = BTreeMap::new();
+    for x in before {
+        domains.entry(canonical_domain(x.0)).or_default().0.get_or_insert(x);
+    }
+    for x in after {
+        domains.entry(canonical_domain(x.0)).or_default().1.get_or_insert(x);
+    }
+
+    domains
+        .into_values()
+        .filter_map(|x| match x {
+            (Some((domain, a)), Some((_, b))) => {
+                let comparison = a.compare(b);
+                if comparison.is_equal() {
+                    None
+                } else {
+                    Some(DomainChange::Changed(domain, comparison))
+                }
+            }
+            (Some((domain, _)), None) => Some(DomainChange::Disappeared(domain)),
+            (None, Some((domain, _))) => Some(DomainChange::Appeared(domain)),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+#[inline(always)]
+fn count_distinct(counts: &mut HashMap<String, usize>, mut keys: Vec<String>) {
+    keys.sort_unstable();
+    keys.dedup();
+    for k in keys {
+        *counts.entry(k).or_default() += 1;
+    }
+}
+
+#[inline(always)]
+fn ratio(a: usize, b: usize) -> f64 {
+    if b == 0 {
+        0.0
+    } else {
+        a as f64 / b as f64
+    }
+}
+
+#[test]
+fn test_corpus_stats() {
+    let documents = [
+        "greenadexchange.com, 1, DIRECT\nGreenAdExchange.com, 2, RESELLER\ncontact=a\ncontact=b",
+        "blueadexchange.com, 1, RESELLER\nunknown\nunknown",
+        "# empty",
+        "",
+    ];
+    let stats: CorpusStats = documents.into_iter().collect();
+
+    assert_eq!(stats.documents, 4);
+    assert_eq!(stats.documents_with_unknown_lines, 1);
+    assert_eq!(stats.lines, 9);
+    assert_eq!(stats.unknown_lines, 2);
+    assert_eq!(stats.records, 3);
+    assert_eq!(stats.direct_records, 1);
+    assert_eq!(stats.reseller_records, 2);
+    assert_eq!(stats.exchanges, HashMap::from([("greenadexchange.com".into(), 1), ("blueadexchange.com".into(), 1)]));
+    assert_eq!(stats.variables, HashMap::from([("contact".into(), 1)]));
+
+    assert_eq!(stats.market_share(), vec![("blueadexchange.com", 0.25), ("greenadexchange.com", 0.25)]);
+    assert_eq!(stats.reseller_density(), 2.0 / 3.0);
+    assert_eq!(stats.variable_adoption("CONTACT"), 0.25);
+    assert_eq!(stats.variable_adoption("ownerdomain"), 0.0);
+    assert_eq!(stats.parse_failure_rate(), 0.25);
+
+    assert_eq!(CorpusStats::from_parallel(&documents), stats);
+
+    let stats: CorpusStats =
+        ["greenadexchange.com, 1, DIRECT\nwww.GreenAdExchange.com., 2, DIRECT"].into_iter().collect();
+    assert_eq!(stats.exchanges, HashMap::from([("greenadexchange.com".into(), 1)]));
+}
+
+#[test]
+fn test_corpus_stats_empty() {
+    let stats = CorpusStats::from_parallel::<&str>(&[]);
+    assert_eq!(stats, CorpusStats::default());
+    assert_eq!(stats.reseller_density(), 0.0);
+    assert_eq!(stats.parse_failure_rate(), 0.0);
+}
+
+#[test]
+fn test_conflict_reports() {
+    let documents = [
+        (
+            "example.com",
+            AdsTxt::parse(
+                "a.com, 1, DIRECT
+A.com, 1, DIRECT
+a.com, 1, DIRECT, cert
+b.com, 2, RESELLER
+b.com, 2, DIRECT
+b.com, 2, DIRECT
+OWNERDOMAIN=example.com
+ownerdomain=Example.com
+MANAGERDOMAIN=m.com
+MANAGERDOMAIN=m.fr, FR
+MANAGERDOMAIN=n.fr, fr",
+            ),
+        ),
+        ("example.org", AdsTxt::parse("a.com, 1, DIRECT\nOWNERDOMAIN=example.org\nOWNERDOMAIN=other.org")),
+        ("example.net", AdsTxt::parse("a.com, 1, DIRECT\na.com, 2, RESELLER\nOWNERDOMAIN=example.net")),
+        ("example.io", AdsTxt::parse("OWNERDOMAIN=example.io\nOWNERDOMAIN=www.Example.io.")),
+    ];
+    let (a, b) = (&documents[0].1, &documents[1].1);
+
+    assert_eq!(conflict_reports(&documents), vec![
+        ConflictReport {
+            publisher: "example.com",
+            duplicate_records: vec![&a.records[1].0, &a.records[5].0],
+            relation_conflicts: vec![(&a.records[4].0, &a.records[3].0)],
+            conflicting_variables: vec![&a.variables[4].0],
+        },
+        ConflictReport {
+            publisher: "example.org",
+            duplicate_records: vec![],
+            relation_conflicts: vec![],
+            conflicting_variables: vec![&b.variables[0].0, &b.variables[1].0],
+        },
+    ]);
+}
+
+#[test]
+fn test_changes() {
+    let before = [
+        ("a.com", AdsTxt::parse("x.com, 1, DIRECT\ncontact=a")),
+        ("B.com", AdsTxt::parse("x.com, 1, DIRECT")),
+        ("c.com", AdsTxt::parse("x.com, 1, DIRECT")),
+    ];
+    let after = [
+        ("d.com", AdsTxt::parse("")),
+        ("a.com", AdsTxt::parse("x.com, 1, RESELLER\ncontact=b")),
+        ("www.b.com.", AdsTxt::parse("X.com, 1, DIRECT # unchanged")),
+    ];
+
+    assert_eq!(changes(&before, &after), vec![
+        DomainChange::Changed("a.com", Comparison {
+            records_only_in_self: vec![&before[0].1.records[0].0],
+            records_only_in_other: vec![&after[1].1.records[0].0],
+            variables_only_in_self: vec![&before[0].1.variables[0].0],
+            variables_only_in_other: vec![&after[1].1.variables[0].0],
+        }),
+        DomainChange::Disappeared("c.com"),
+        DomainChange::Appeared("d.com"),
+    ]);
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::AdsTxt;
+
+impl AdsTxt<'_> {
+    /// Converts the records to an Arrow [`RecordBatch`] with the `Utf8` columns `domain`, `account_id`,
+    /// `relation`, and the nullable `authority_id` and `extension`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    /// use arrow_array::{Array, StringArray};
+    ///
+    /// let batch = AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nblueadexchange.com, XF436, RESELLER").to_arrow();
+    /// assert_eq!(batch.num_rows(), 2);
+    /// let relation = batch.column_by_name("relation").unwrap().as_any().downcast_ref::<StringArray>().unwrap();
+    /// assert_eq!(relation.value(1), "RESELLER");
+    /// ```
+    pub fn to_arrow(&self) -> RecordBatch {
+        batch(vec![
+            ("domain", strings(self.records.iter().map(|(r, _)| Some(r.domain))), false),
+            ("account_id", strings(self.records.iter().map(|(r, _)| Some(r.account_id))), false),
+            ("relation", strings(self.records.iter().map(|(r, _)| Some(r.relation.as_str()))), false),
+            ("authority_id", strings(self.records.iter().map(|(r, _)| r.authority_id)), true),
+            ("extension", strings(self.records.iter().map(|(_, e)| e.as_ref().map(|x| x.0))), true),
+        ])
+    }
+
+    /// Converts the variables to an Arrow [`RecordBatch`] with the `Utf8` columns `name`, `value`, and the
+    /// nullable `extension`.
+    pub fn variables_to_arrow(&self) -> RecordBatch {
+        batch(vec![
+            ("name", strings(self.variables.iter().map(|(v, _)| Some(v.name))), false),
+            ("value", strings(self.variables.iter().map(|(v, _)| Some(v.value))), false),
+            ("extension", strings(self.variables.iter().map(|(_, e)| e.as_ref().map(|x| x.0))), true),
+        ])
+    }
+}
+
+#[inline(always)]
+fn strings<'a>(values: impl Iterator<Item = Option<&'a str>>) -> ArrayRef {
+    Arc::new(values.collect::<StringArray>())
+}
+
+fn batch(columns: Vec<(&str, ArrayRef, bool)>) -> RecordBatch {
+    let schema = Schema::new(
+        columns.iter().map(|(name, _, nullable)| Field::new(*name, DataType::Utf8, *nullable)).collect::<Vec<_>>(),
+    );
+    RecordBatch::try_new(Arc::new(schema), columns.into_iter().map(|(_, x, _)| x).collect())
+        .expect("columns match the schema")
+}
+
+#[test]
+fn test_to_arrow() {
+    use arrow_array::Array;
+
+    let txt = AdsTxt::parse(
+        "greenadexchange.com, 1, DIRECT, d75815a79 ; ext\nblueadexchange.com, 2, RESELLER\ncontact=adops@example.com",
+    );
+
+    let batch = txt.to_arrow();
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.schema().fields().iter().map(|x| (x.name().as_str(), x.is_nullable())).collect::<Vec<_>>(), vec![
+        ("domain", false),
+        ("account_id", false),
+        ("relation", false),
+        ("authority_id", true),
+        ("extension", true)
+    ]);
+    let column = |name| batch.column_by_name(name).unwrap().as_any().downcast_ref::<StringArray>().unwrap().clone();
+    assert_eq!(column("domain").iter().collect::<Vec<_>>(), vec![
+        Some("greenadexchange.com"),
+        Some("blueadexchange.com")
+    ]);
+    assert_eq!(column("authority_id").iter().collect::<Vec<_>>(), vec![Some("d75815a79"), None]);
+    assert_eq!(column("extension").null_count(), 1);
+
+    let batch = txt.variables_to_arrow();
+    assert_eq!(batch.num_rows(), 1);
+    assert_eq!(batch.num_columns(), 3);
+
+    assert_eq!(AdsTxt::parse("").to_arrow().num_rows(), 0);
+}
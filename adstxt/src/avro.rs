@@ -0,0 +1,179 @@
+//! [Avro] object container encoding of records, for data lake pipelines standardized on Avro.
+//!
+//! Every record becomes one Avro record of [`SCHEMA`], tagged with the domain its ads.txt was fetched from. The
+//! schema is embedded in the file header, so the output can be read by any Avro implementation.
+//!
+//! [Avro]: https://avro.apache.org/docs/1.11.1/specification/
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    io::{self, Write},
+};
+
+use crate::AdsTxt;
+
+/// Avro schema of the records written by [`write()`].
+pub const SCHEMA: &str = r#"{"type":"record","name":"Record","namespace":"adstxt","fields":[{"name":"publisher","type":"string"},{"name":"domain","type":"string"},{"name":"account_id","type":"string"},{"name":"relation","type":"string"},{"name":"authority_id","type":["null","string"]},{"name":"extension","type":["null","string"]}]}"#;
+
+const MAGIC: &[u8] = b"Obj\x01";
+
+/// Writes the records of `(domain, ads.txt)` pairs as an uncompressed Avro object container file.
+///
+/// Each document with at least one record is written as one block.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// let documents = [("example.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT"))];
+/// let mut buf = Vec::new();
+/// avro::write(&mut buf, &documents).unwrap();
+/// assert!(buf.starts_with(b"Obj\x01"));
+/// ```
+pub fn write<W: Write>(mut writer: W, documents: &[(&str, AdsTxt<'_>)]) -> io::Result<()> {
+    let state = RandomState::new();
+    let sync = [state.hash_one(0u8).to_le_bytes(), state.hash_one(1u8).to_le_bytes()].concat();
+
+    let mut header = MAGIC.to_vec();
+    write_long(&mut header, 2);
+    for (key, value) in [("avro.schema", SCHEMA), ("avro.codec", "null")] {
+        write_bytes(&mut header, key.as_bytes());
+        write_bytes(&mut header, value.as_bytes());
+    }
+    write_long(&mut header, 0);
+    header.extend_from_slice(&sync);
+    writer.write_all(&header)?;
+
+    for (publisher, txt) in documents.iter().filter(|(_, x)| !x.records.is_empty()) {
+        let mut block = Vec::new();
+        for (record, extension) in &txt.records {
+            write_bytes(&mut block, publisher.as_bytes());
+            write_bytes(&mut block, record.domain.as_bytes());
+            write_bytes(&mut block, record.account_id.as_bytes());
+            write_bytes(&mut block, record.relation.as_str().as_bytes());
+            write_optional(&mut block, record.authority_id);
+            write_optional(&mut block, extension.as_ref().map(|x| x.0));
+        }
+
+        let mut prefix = Vec::new();
+        write_long(&mut prefix, txt.records.len() as i64);
+        write_long(&mut prefix, block.len() as i64);
+        writer.write_all(&prefix)?;
+        writer.write_all(&block)?;
+        writer.write_all(&sync)?;
+    }
+    writer.flush()
+}
+
+/// Writes a zigzag encoded variable-length integer.
+#[inline(always)]
+fn write_long(buf: &mut Vec<u8>, n: i64) {
+    let mut n = ((n << 1) ^ (n >> 63)) as u64;
+    while n >= 0x80 {
+        buf.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+#[inline(always)]
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(buf, bytes.len() as i64);
+    buf.extend_from_slice(bytes);
+}
+
+/// Writes a `["null", "string"]` union.
+#[inline(always)]
+fn write_optional(buf: &mut Vec<u8>, value: Option<&str>) {
+    match value {
+        Some(x) => {
+            write_long(buf, 1);
+            write_bytes(buf, x.as_bytes());
+        }
+        None => write_long(buf, 0),
+    }
+}
+
+#[test]
+fn test_write_long() {
+    let encode = |n| {
+        let mut buf = Vec::new();
+        write_long(&mut buf, n);
+        buf
+    };
+    assert_eq!(encode(0), [0x00]);
+    assert_eq!(encode(-1), [0x01]);
+    assert_eq!(encode(1), [0x02]);
+    assert_eq!(encode(-64), [0x7f]);
+    assert_eq!(encode(64), [0x80, 0x01]);
+    assert_eq!(encode(i64::MIN), [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+}
+
+#[test]
+fn test_write() {
+    fn read_long(buf: &mut &[u8]) -> i64 {
+        let (mut n, mut shift) = (0u64, 0);
+        loop {
+            let (b, rest) = buf.split_first().unwrap();
+            *buf = rest;
+            n |= u64::from(b & 0x7f) << shift;
+            shift += 7;
+            if b & 0x80 == 0 {
+                return (n >> 1) as i64 ^ -((n & 1) as i64);
+            }
+        }
+    }
+    fn read_string<'a>(buf: &mut &'a [u8]) -> &'a str {
+        let len = read_long(buf) as usize;
+        let (s, rest) = buf.split_at(len);
+        *buf = rest;
+        std::str::from_utf8(s).unwrap()
+    }
+    fn read_optional<'a>(buf: &mut &'a [u8]) -> Option<&'a str> {
+        match read_long(buf) {
+            0 => None,
+            _ => Some(read_string(buf)),
+        }
+    }
+
+    let documents = [
+        ("a.com", AdsTxt::parse("greenadexchange.com, 1, DIRECT, d75815a79\nblueadexchange.com, 2, RESELLER ; ext")),
+        ("b.com", AdsTxt::parse("contact=adops@b.com")),
+        ("c.com", AdsTxt::parse("silverssp.com, 3, DIRECT")),
+    ];
+    let mut out = Vec::new();
+    write(&mut out, &documents).unwrap();
+
+    let mut buf = out.strip_prefix(MAGIC).unwrap();
+    assert_eq!(read_long(&mut buf), 2);
+    assert_eq!((read_string(&mut buf), read_string(&mut buf)), ("avro.schema", SCHEMA));
+    assert_eq!((read_string(&mut buf), read_string(&mut buf)), ("avro.codec", "null"));
+    assert_eq!(read_long(&mut buf), 0);
+    let (sync, mut buf) = buf.split_at(16);
+
+    let mut rows = Vec::new();
+    while !buf.is_empty() {
+        let count = read_long(&mut buf);
+        let size = read_long(&mut buf) as usize;
+        let (mut block, rest) = buf.split_at(size);
+        for _ in 0..count {
+            rows.push((
+                read_string(&mut block),
+                read_string(&mut block),
+                read_string(&mut block),
+                read_string(&mut block),
+                read_optional(&mut block),
+                read_optional(&mut block),
+            ));
+        }
+        assert!(block.is_empty());
+        assert_eq!(&rest[..16], sync);
+        buf = &rest[16..];
+    }
+    assert_eq!(rows, vec![
+        ("a.com", "greenadexchange.com", "1", "DIRECT", Some("d75815a79"), None),
+        ("a.com", "blueadexchange.com", "2", "RESELLER", None, Some("ext")),
+        ("c.com", "silverssp.com", "3", "DIRECT", None, None),
+    ]);
+}
@@ -0,0 +1,126 @@
+use crate::{AdsTxt, LineData};
+
+/// Number of bytes at the start of a body searched for HTML markup.
+const HTML_PREFIX_LEN: usize = 1024;
+
+/// Represents the kind of a response body served for an ads.txt URL.
+///
+/// See also [`AdsTxt::classify_body`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BodyKind {
+    /// The body has at least one record or variable.
+    AdsTxt,
+    /// The body only has blank lines and comments.
+    Empty,
+    /// The body is an HTML page or has no record among other lines, e.g. a 404 or landing page served with a
+    /// successful status.
+    SoftNotFound,
+}
+
+impl AdsTxt<'_> {
+    /// Classifies a response body served for an ads.txt URL, so that error and landing pages served with a
+    /// successful status are not stored as ads.txt files.
+    ///
+    /// A body is a [`BodyKind::SoftNotFound`] if it starts with HTML markup (`<!DOCTYPE html>`, `<html>`, `<head>`
+    /// or `<body>`, after a byte order mark and whitespace), or if none of its lines parses as a record while
+    /// some are unrecognized.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// assert_eq!(AdsTxt::classify_body("greenadexchange.com, 12345, DIRECT"), BodyKind::AdsTxt);
+    /// assert_eq!(AdsTxt::classify_body("# no sellers yet\n"), BodyKind::Empty);
+    /// assert_eq!(AdsTxt::classify_body("<!DOCTYPE html>\n<html>Not Found</html>"), BodyKind::SoftNotFound);
+    /// assert_eq!(AdsTxt::classify_body("Welcome to example.com!\nCheck out our offers."), BodyKind::SoftNotFound);
+    /// ```
+    pub fn classify_body(body: &str) -> BodyKind {
+        if starts_with_html(body) {
+            return BodyKind::SoftNotFound;
+        }
+
+        let (mut records, mut variables, mut unknown) = (0, 0, 0);
+        for line in AdsTxt::parse_lines(body) {
+            match line {
+                LineData::Record { .. } => records += 1,
+                LineData::Variable { .. } => variables += 1,
+                LineData::Unknown(_) => unknown += 1,
+                _ => {}
+            }
+        }
+        match (records, variables, unknown) {
+            (0, _, 1..) => BodyKind::SoftNotFound,
+            (0, 0, 0) => BodyKind::Empty,
+            _ => BodyKind::AdsTxt,
+        }
+    }
+
+    /// Classifies a response by its `Content-Type` header value and body.
+    ///
+    /// A `text/html` content type is a [`BodyKind::SoftNotFound`]; otherwise the body is classified with
+    /// [`AdsTxt::classify_body`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let body = "greenadexchange.com, 12345, DIRECT";
+    /// assert_eq!(AdsTxt::classify_response(Some("text/plain; charset=utf-8"), body), BodyKind::AdsTxt);
+    /// assert_eq!(AdsTxt::classify_response(Some("Text/HTML; charset=utf-8"), body), BodyKind::SoftNotFound);
+    /// ```
+    pub fn classify_response(content_type: Option<&str>, body: &str) -> BodyKind {
+        let media_type = content_type.and_then(|x| x.split(';').next()).map(str::trim);
+        if media_type.is_some_and(|x| x.eq_ignore_ascii_case("text/html")) {
+            BodyKind::SoftNotFound
+        } else {
+            AdsTxt::classify_body(body)
+        }
+    }
+
+    /// Returns `true` if [`AdsTxt::classify_body`] classifies `body` as a [`BodyKind::SoftNotFound`].
+    pub fn looks_like_soft_404(body: &str) -> bool {
+        AdsTxt::classify_body(body) == BodyKind::SoftNotFound
+    }
+}
+
+fn starts_with_html(body: &str) -> bool {
+    let body = body.trim_start_matches('\u{feff}').trim_start();
+    if !body.starts_with('<') {
+        return false;
+    }
+    let end = (0..=HTML_PREFIX_LEN.min(body.len())).rev().find(|x| body.is_char_boundary(*x)).unwrap_or_default();
+    let prefix = body[..end].to_ascii_lowercase();
+    ["<!doctype html", "<html", "<head", "<body"].iter().any(|x| prefix.contains(x))
+}
+
+#[test]
+fn test_classify_body() {
+    assert_eq!(AdsTxt::classify_body(""), BodyKind::Empty);
+    assert_eq!(AdsTxt::classify_body("\n# comment\n\n"), BodyKind::Empty);
+    assert_eq!(AdsTxt::classify_body("contact=adops@example.com"), BodyKind::AdsTxt);
+    assert_eq!(AdsTxt::classify_body("greenadexchange.com, 1, DIRECT\nunknown\nunknown"), BodyKind::AdsTxt);
+    assert_eq!(AdsTxt::classify_body("contact=adops@example.com\nunknown"), BodyKind::SoftNotFound);
+    assert_eq!(AdsTxt::classify_body("\u{feff}  <HTML><p>a, b, DIRECT</p></HTML>"), BodyKind::SoftNotFound);
+    assert_eq!(AdsTxt::classify_body("<p>a, b, DIRECT</p>"), BodyKind::AdsTxt);
+    assert_eq!(AdsTxt::classify_body(&format!("<{}<html>\na, b, DIRECT", "é".repeat(600))), BodyKind::AdsTxt);
+    assert!(AdsTxt::looks_like_soft_404(include_str!("../fixtures/html_error_page.txt")));
+    assert!(!AdsTxt::looks_like_soft_404(include_str!("../fixtures/network_reseller.txt")));
+}
+
+#[test]
+fn test_classify_response() {
+    assert_eq!(AdsTxt::classify_response(None, "a, b, DIRECT"), BodyKind::AdsTxt);
+    assert_eq!(AdsTxt::classify_response(Some("text/html"), "a, b, DIRECT"), BodyKind::SoftNotFound);
+    assert_eq!(AdsTxt::classify_response(Some("text/plain"), "<html>"), BodyKind::SoftNotFound);
+    assert_eq!(AdsTxt::classify_response(Some("text/htmlx"), ""), BodyKind::Empty);
+}
+
+#[cfg(feature = "fixtures")]
+#[test]
+fn test_classify_fixtures() {
+    for fixture in crate::fixtures::FIXTURES {
+        let expected = if fixture.name == "html_error_page" { BodyKind::SoftNotFound } else { BodyKind::AdsTxt };
+        assert_eq!(AdsTxt::classify_body(fixture.input), expected, "{}", fixture.name);
+    }
+}
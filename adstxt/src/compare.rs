@@ -0,0 +1,137 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{AdsTxt, Record, Variable};
+
+/// Represents the result of an order-insensitive comparison of two ads.txt files.
+///
+/// See also [`AdsTxt::compare`].
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Comparison<'a> {
+    /// Records only found in the left-hand file.
+    pub records_only_in_self: Vec<&'a Record<'a>>,
+    /// Records only found in the right-hand file.
+    pub records_only_in_other: Vec<&'a Record<'a>>,
+    /// Variables only found in the left-hand file.
+    pub variables_only_in_self: Vec<&'a Variable<'a>>,
+    /// Variables only found in the right-hand file.
+    pub variables_only_in_other: Vec<&'a Variable<'a>>,
+}
+
+impl Comparison<'_> {
+    /// Returns `true` if both files are semantically equal.
+    pub fn is_equal(&self) -> bool {
+        self.records_only_in_self.is_empty()
+            && self.records_only_in_other.is_empty()
+            && self.variables_only_in_self.is_empty()
+            && self.variables_only_in_other.is_empty()
+    }
+}
+
+impl AdsTxt<'_> {
+    /// Compares two files ignoring the order of lines, duplicated lines and comments.
+    ///
+    /// Records are compared by their [`RecordKey`](crate::RecordKey) and certification authority ID: the
+    /// advertising system domain by its [`canonical_domain`](crate::canonical_domain), the account ID, relation and
+    /// certification authority ID exactly. Variables are compared by their name and value, case-insensitively. Each
+    /// side of the report lists the first occurrence of every entry missing from the other file. Extension data is
+    /// not taken into account.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let a = AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nblueadexchange.com, XF436, DIRECT");
+    /// let b = AdsTxt::parse("blueadexchange.com, XF436, DIRECT\nGreenAdExchange.com, 12345, DIRECT # comment");
+    /// let c = AdsTxt::parse("blueadexchange.com, XF436, DIRECT\ncontact=adops@example.com");
+    ///
+    /// assert!(a.semantically_eq(&b));
+    ///
+    /// let comparison = a.compare(&c);
+    /// assert_eq!(comparison.records_only_in_self, vec![&a.records[0].0]);
+    /// assert_eq!(comparison.variables_only_in_other, vec![&c.variables[0].0]);
+    /// ```
+    pub fn compare<'a>(&'a self, other: &'a AdsTxt<'_>) -> Comparison<'a> {
+        let (records_only_in_self, records_only_in_other) =
+            difference(&self.records, &other.records, |(r, _)| (r.key(), r.authority_id));
+        let (variables_only_in_self, variables_only_in_other) =
+            difference(&self.variables, &other.variables, |(v, _)| {
+                (v.name.to_ascii_lowercase(), v.value.to_ascii_lowercase())
+            });
+
+        Comparison {
+            records_only_in_self: records_only_in_self.into_iter().map(|(r, _)| r).collect(),
+            records_only_in_other: records_only_in_other.into_iter().map(|(r, _)| r).collect(),
+            variables_only_in_self: variables_only_in_self.into_iter().map(|(v, _)| v).collect(),
+            variables_only_in_other: variables_only_in_other.into_iter().map(|(v, _)| v).collect(),
+        }
+    }
+
+    /// Returns `true` if both files contain the same records and variables, ignoring the order of lines,
+    /// duplicated lines and comments.
+    ///
+    /// See also [`AdsTxt::compare`].
+    pub fn semantically_eq(&self, other: &AdsTxt<'_>) -> bool {
+        self.compare(other).is_equal()
+    }
+}
+
+fn difference<'a, T, K: Eq + Hash>(
+    left: &'a [T],
+    right: &'a [T],
+    key: impl Fn(&'a T) -> K,
+) -> (Vec<&'a T>, Vec<&'a T>) {
+    let only_in = |a: &'a [T], b: &'a [T]| {
+        let b: HashSet<_> = b.iter().map(&key).collect();
+        let mut seen = HashSet::new();
+        a.iter()
+            .filter(|x| {
+                let k = key(x);
+                !b.contains(&k) && seen.insert(k)
+            })
+            .collect()
+    };
+    (only_in(left, right), only_in(right, left))
+}
+
+#[test]
+fn test_compare() {
+    let a = AdsTxt::parse(
+        "greenadexchange.com, 12345, DIRECT, d75815a79
+greenadexchange.com, 12345, DIRECT, d75815a79
+silverssp.com, 9675, RESELLER
+contact=adops@example.com
+subdomain=divisionone.example.com",
+    );
+    let b = AdsTxt::parse(
+        "# comment
+SUBDOMAIN=DivisionOne.example.com
+GREENADEXCHANGE.COM, 12345, DIRECT, d75815a79
+silverssp.com, 9675, DIRECT
+contact=http://example.com/contact-us",
+    );
+
+    assert!(a.semantically_eq(&a));
+    assert!(!a.semantically_eq(&b));
+    assert_eq!(a.compare(&b), Comparison {
+        records_only_in_self: vec![&a.records[2].0],
+        records_only_in_other: vec![&b.records[1].0],
+        variables_only_in_self: vec![&a.variables[0].0],
+        variables_only_in_other: vec![&b.variables[1].0],
+    });
+}
+
+#[test]
+fn test_compare_authority_id() {
+    let a = AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79");
+    let b = AdsTxt::parse("greenadexchange.com, 12345, DIRECT");
+    assert!(!a.semantically_eq(&b));
+    assert!(AdsTxt::parse("greenadexchange.com, 12345, DIRECT ; ext").semantically_eq(&b));
+}
+
+#[test]
+fn test_compare_dedup() {
+    let a = AdsTxt::parse("greenadexchange.com, 12345, DIRECT\ngreenadexchange.com, 12345, DIRECT");
+    let b = AdsTxt::parse("");
+    assert_eq!(a.compare(&b).records_only_in_self, vec![&a.records[0].0]);
+}
@@ -0,0 +1,133 @@
+use crate::AdsTxt;
+
+/// Represents the separator line between the documents of a concatenated ads.txt dump.
+///
+/// A line is a separator if, once trimmed, it starts with `prefix` and ends with `suffix`. The text in between
+/// is the domain of the following document. The default matches `#=== example.com ===`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CorpusDelimiter<'d> {
+    pub prefix: &'d str,
+    pub suffix: &'d str,
+}
+
+impl Default for CorpusDelimiter<'_> {
+    fn default() -> Self {
+        CorpusDelimiter { prefix: "#===", suffix: "===" }
+    }
+}
+
+impl CorpusDelimiter<'_> {
+    #[inline(always)]
+    fn domain<'a>(&self, line: &'a str) -> Option<&'a str> {
+        let line = line.trim();
+        if line.len() < self.prefix.len() + self.suffix.len() {
+            return None;
+        }
+        let domain = line.strip_prefix(self.prefix)?.strip_suffix(self.suffix)?.trim();
+        if domain.is_empty() {
+            None
+        } else {
+            Some(domain)
+        }
+    }
+
+    /// Returns the offset of the first separator line in `data`, the offset of the line after it and its domain.
+    #[inline(always)]
+    fn find<'a>(&self, data: &'a str) -> Option<(usize, usize, &'a str)> {
+        let mut start = 0;
+        for line in data.split_inclusive('\n') {
+            let end = start + line.len();
+            if let Some(domain) = self.domain(line) {
+                return Some((start, end, domain));
+            }
+            start = end;
+        }
+        None
+    }
+}
+
+/// Iterator over the documents of a concatenated ads.txt dump.
+///
+/// See also [`AdsTxt::parse_corpus`].
+#[derive(Debug, Clone)]
+pub struct CorpusDocuments<'a, 'd> {
+    delimiter: CorpusDelimiter<'d>,
+    next: Option<(&'a str, &'a str)>,
+}
+
+impl<'a> Iterator for CorpusDocuments<'a, '_> {
+    type Item = (&'a str, AdsTxt<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (domain, rest) = self.next.take()?;
+        let document = match self.delimiter.find(rest) {
+            Some((start, end, next)) => {
+                self.next = Some((next, &rest[end..]));
+                &rest[..start]
+            }
+            None => rest,
+        };
+        Some((domain, AdsTxt::parse(document)))
+    }
+}
+
+impl AdsTxt<'_> {
+    /// Parse a dump of many ads.txt files concatenated with separator lines.
+    ///
+    /// Yields `(domain, ads.txt)` pairs lazily in the order of the dump. Data before the first separator line is
+    /// ignored.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let dump = "#=== example.com ===
+    /// greenadexchange.com, 12345, DIRECT
+    /// #=== example.org ===
+    /// blueadexchange.com, XF436, RESELLER
+    /// contact=adops@example.org";
+    ///
+    /// let documents: Vec<_> = AdsTxt::parse_corpus(dump, CorpusDelimiter::default()).collect();
+    /// assert_eq!(documents.len(), 2);
+    /// assert_eq!(documents[0].0, "example.com");
+    /// assert_eq!(documents[1].0, "example.org");
+    /// assert_eq!(documents[1].1.variables.len(), 1);
+    /// ```
+    pub fn parse_corpus<'a, 'd>(data: &'a str, delimiter: CorpusDelimiter<'d>) -> CorpusDocuments<'a, 'd> {
+        let next = delimiter.find(data).map(|(_, end, domain)| (domain, &data[end..]));
+        CorpusDocuments { delimiter, next }
+    }
+}
+
+#[test]
+fn test_corpus_delimiter() {
+    let delimiter = CorpusDelimiter::default();
+    assert_eq!(delimiter.domain("#=== example.com ==="), Some("example.com"));
+    assert_eq!(delimiter.domain("  #===example.com===\r\n"), Some("example.com"));
+    assert_eq!(delimiter.domain("#=== ==="), None);
+    assert_eq!(delimiter.domain("#===="), None);
+    assert_eq!(delimiter.domain("# example.com"), None);
+
+    let delimiter = CorpusDelimiter { prefix: "---- ", suffix: "" };
+    assert_eq!(delimiter.domain("---- example.com"), Some("example.com"));
+}
+
+#[test]
+fn test_parse_corpus() {
+    let dump = "garbage, before, DIRECT
+#=== a.com ===
+f1, f2, DIRECT
+#=== b.com ===
+#=== c.com ===
+f3, f4, RESELLER
+name=value
+";
+    let documents: Vec<_> = AdsTxt::parse_corpus(dump, CorpusDelimiter::default()).collect();
+    assert_eq!(documents, vec![
+        ("a.com", AdsTxt::parse("f1, f2, DIRECT\n")),
+        ("b.com", AdsTxt::parse("")),
+        ("c.com", AdsTxt::parse("f3, f4, RESELLER\nname=value\n")),
+    ]);
+
+    assert_eq!(AdsTxt::parse_corpus("f1, f2, DIRECT", CorpusDelimiter::default()).count(), 0);
+}
@@ -0,0 +1,191 @@
+//! Parsed ads.txt types holding either borrowed or owned strings.
+//!
+//! The types of the crate root borrow from the parsed source, which makes them cheap to produce but impossible to
+//! keep once the source is dropped, or to mutate with new strings. The types of this module mirror them with
+//! [`Cow`] fields: converting a parse result is zero-copy, [`AdsTxt::into_owned`] detaches it from the source, and
+//! [`AdsTxt::as_borrowed`] gives back a [`crate::AdsTxt`] to use with the rest of the crate.
+//!
+//! With the `serde` feature, these types deserialize from any format, borrowing strings when the deserializer
+//! can lend them and allocating otherwise.
+//!
+//! # Example
+//! ```rust
+//! # use adstxt::*;
+//!
+//! let source = String::from("greenadexchange.com, 12345, DIRECT");
+//! let mut txt = cow::AdsTxt::from(AdsTxt::parse(&source)).into_owned();
+//! drop(source);
+//!
+//! let account_id = format!("{}", 67890);
+//! txt.records.push((
+//!     cow::Record {
+//!         domain: "blueadexchange.com".into(),
+//!         account_id: account_id.into(),
+//!         relation: "RESELLER".into(),
+//!         authority_id: None,
+//!     },
+//!     None,
+//! ));
+//! assert_eq!(
+//!     txt.as_borrowed().to_string(),
+//!     "greenadexchange.com, 12345, DIRECT\nblueadexchange.com, 67890, RESELLER\n"
+//! );
+//! ```
+
+use std::borrow::Cow;
+
+use crate::{parser::parse_relation, Extension, Relation};
+
+/// Represents the records and variables of ads.txt. See [`crate::AdsTxt`].
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdsTxt<'a> {
+    /// Records and their extension data.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub records: Vec<(Record<'a>, Option<Cow<'a, str>>)>,
+    /// Variables and their extension data.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub variables: Vec<(Variable<'a>, Option<Cow<'a, str>>)>,
+}
+
+/// Represents record of ads.txt. See [`crate::Record`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record<'a> {
+    /// FIELD #1: Domain name of the advertising system.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub domain: Cow<'a, str>,
+    /// FIELD #2: Publisher's Account ID.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub account_id: Cow<'a, str>,
+    /// FIELD #3: Type of Account/Relationship as written in ads.txt. See [`Record::relation`].
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub relation: Cow<'a, str>,
+    /// FIELD #4: Certification Authority ID.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub authority_id: Option<Cow<'a, str>>,
+}
+
+/// Represents variable record. See [`crate::Variable`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variable<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub name: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub value: Cow<'a, str>,
+}
+
+impl AdsTxt<'_> {
+    /// Returns a copy that owns all of its strings.
+    pub fn into_owned(self) -> AdsTxt<'static> {
+        AdsTxt {
+            records: self.records.into_iter().map(|(r, e)| (r.into_owned(), e.map(owned))).collect(),
+            variables: self.variables.into_iter().map(|(v, e)| (v.into_owned(), e.map(owned))).collect(),
+        }
+    }
+
+    /// Returns a [`crate::AdsTxt`] borrowing from `self`.
+    pub fn as_borrowed(&self) -> crate::AdsTxt<'_> {
+        crate::AdsTxt {
+            records: self.records.iter().map(|(r, e)| (r.as_borrowed(), e.as_deref().map(Extension))).collect(),
+            variables: self.variables.iter().map(|(v, e)| (v.as_borrowed(), e.as_deref().map(Extension))).collect(),
+        }
+    }
+}
+
+impl Record<'_> {
+    /// Returns the parsed FIELD #3.
+    pub fn relation(&self) -> Relation<'_> {
+        parse_relation(&self.relation)
+    }
+
+    /// Returns a copy that owns all of its strings.
+    pub fn into_owned(self) -> Record<'static> {
+        Record {
+            domain: owned(self.domain),
+            account_id: owned(self.account_id),
+            relation: owned(self.relation),
+            authority_id: self.authority_id.map(owned),
+        }
+    }
+
+    /// Returns a [`crate::Record`] borrowing from `self`.
+    pub fn as_borrowed(&self) -> crate::Record<'_> {
+        crate::Record {
+            domain: &self.domain,
+            account_id: &self.account_id,
+            relation: self.relation(),
+            authority_id: self.authority_id.as_deref(),
+        }
+    }
+}
+
+impl Variable<'_> {
+    /// Returns a copy that owns all of its strings.
+    pub fn into_owned(self) -> Variable<'static> {
+        Variable { name: owned(self.name), value: owned(self.value) }
+    }
+
+    /// Returns a [`crate::Variable`] borrowing from `self`.
+    pub fn as_borrowed(&self) -> crate::Variable<'_> {
+        crate::Variable { name: &self.name, value: &self.value }
+    }
+}
+
+impl<'a> From<crate::AdsTxt<'a>> for AdsTxt<'a> {
+    fn from(txt: crate::AdsTxt<'a>) -> Self {
+        AdsTxt {
+            records: txt.records.into_iter().map(|(r, e)| (r.into(), e.map(|x| x.0.into()))).collect(),
+            variables: txt.variables.into_iter().map(|(v, e)| (v.into(), e.map(|x| x.0.into()))).collect(),
+        }
+    }
+}
+
+impl<'a> From<crate::Record<'a>> for Record<'a> {
+    fn from(record: crate::Record<'a>) -> Self {
+        Record {
+            domain: record.domain.into(),
+            account_id: record.account_id.into(),
+            relation: record.relation.as_str().into(),
+            authority_id: record.authority_id.map(Into::into),
+        }
+    }
+}
+
+impl<'a> From<crate::Variable<'a>> for Variable<'a> {
+    fn from(variable: crate::Variable<'a>) -> Self {
+        Variable { name: variable.name.into(), value: variable.value.into() }
+    }
+}
+
+#[inline(always)]
+fn owned(s: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(s.into_owned())
+}
+
+#[test]
+fn test_cow() {
+    let source = "greenadexchange.com, 1, DIRECT, d75815a79 ; ext\nsilverssp.com, 2, other\ncontact=adops@example.com";
+    let parsed = crate::AdsTxt::parse(source);
+
+    let txt = AdsTxt::from(parsed.clone());
+    assert!(matches!(txt.records[0].0.domain, Cow::Borrowed(_)));
+    assert_eq!(txt.records[0].1.as_deref(), Some("ext"));
+    assert_eq!(txt.records[1].0.relation(), Relation::Unknown("other"));
+    assert_eq!(txt.as_borrowed(), parsed);
+
+    let owned = txt.into_owned();
+    assert!(matches!(owned.records[0].0.domain, Cow::Owned(_)));
+    assert!(matches!(owned.variables[0].0.value, Cow::Owned(_)));
+    assert_eq!(owned.as_borrowed(), parsed);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_cow_from_yaml() {
+    let txt = crate::AdsTxt::parse("greenadexchange.com, 1, DIRECT ; ext\ncontact=adops@example.com");
+    let yaml = txt.to_yaml().unwrap();
+    let deserialized: AdsTxt = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(deserialized.as_borrowed(), txt);
+}
@@ -0,0 +1,255 @@
+use std::{fmt, ops::Range};
+
+use crate::{lenient::strip_domain_url, parser::split_line, AdsTxt, Relation, SpannedLineData};
+
+/// Represents how serious a [`Diagnostic`] is.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// The data is accepted but does not follow the spec.
+    Warning,
+    /// The data is ignored by compliant consumers.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Represents a finding about ads.txt data, pointing at byte offsets of the source.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Byte offsets of the offending data in the source.
+    pub span: Range<usize>,
+    pub message: String,
+    /// Suggestion on how to fix the data.
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic with [`Severity::Warning`].
+    pub fn warning(span: Range<usize>, message: impl Into<String>) -> Self {
+        Diagnostic { severity: Severity::Warning, span, message: message.into(), hint: None }
+    }
+
+    /// Creates a diagnostic with [`Severity::Error`].
+    pub fn error(span: Range<usize>, message: impl Into<String>) -> Self {
+        Diagnostic { severity: Severity::Error, span, message: message.into(), hint: None }
+    }
+
+    /// Sets the hint.
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Returns the 1-based line and column numbers of the start of the span.
+    ///
+    /// The column is counted in characters.
+    pub fn location(&self, source: &str) -> (usize, usize) {
+        let start = self.span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |x| x + 1);
+        (source[..start].matches('\n').count() + 1, source[line_start..start].chars().count() + 1)
+    }
+
+    /// Renders the diagnostic with the offending line of `source` and a caret under the span, in the style of
+    /// rustc.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let source = "# comment\ngreenadexchange.com, 12345, DIRCT";
+    /// let diagnostic = Diagnostic::error(38..43, "unknown relation `DIRCT`").with_hint("expected `DIRECT` or `RESELLER`");
+    ///
+    /// assert_eq!(
+    ///     diagnostic.render(source),
+    ///     "error: unknown relation `DIRCT`
+    ///  --> 2:29
+    ///   |
+    /// 2 | greenadexchange.com, 12345, DIRCT
+    ///   |                             ^^^^^
+    ///   = help: expected `DIRECT` or `RESELLER`
+    /// "
+    /// );
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let (line, column) = self.location(source);
+        let start = self.span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |x| x + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |x| start + x);
+        let text = source[line_start..line_end].trim_end_matches('\r');
+        let end = self.span.end.clamp(start, line_start + text.len());
+        let carets = source[start..end].chars().count().max(1);
+
+        let gutter = " ".repeat(line.to_string().len());
+        let mut buf = format!("{}: {}\n", self.severity, self.message);
+        buf.push_str(&format!("{gutter}--> {line}:{column}\n"));
+        buf.push_str(&format!("{gutter} |\n"));
+        buf.push_str(&format!("{line} | {text}\n"));
+        buf.push_str(&format!("{gutter} | {}{}\n", " ".repeat(column - 1), "^".repeat(carets)));
+        if let Some(hint) = &self.hint {
+            buf.push_str(&format!("{gutter} = help: {hint}\n"));
+        }
+        buf
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl AdsTxt<'_> {
+    /// Returns the diagnostics of the parser for the contents of ads.txt.
+    ///
+    /// Lines that are neither a record, a variable nor a comment are reported as warnings. Records with an empty
+    /// domain, account ID or relation field, an empty field followed by other fields (e.g.
+    /// `greenadexchange.com, 12345, DIRECT, , d75815a79`) or an unknown relation are reported as errors. Trailing
+    /// empty fields (e.g. `greenadexchange.com, 12345, DIRECT,`) are not reported since the parser treats them
+    /// as absent.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let source = "greenadexchange.com, 12345, DIRECT\nunknown";
+    /// let diagnostics = AdsTxt::diagnose(source);
+    /// assert_eq!(diagnostics, vec![Diagnostic::warning(35..42, "unrecognized line")
+    ///     .with_hint("expected a record, a variable or a comment")]);
+    /// ```
+    pub fn diagnose(data: &str) -> Vec<Diagnostic> {
+        AdsTxt::parse_spanned(data)
+            .into_iter()
+            .filter_map(|line| match line.value {
+                SpannedLineData::Record { record, .. } => {
+                    if record.domain.value.is_empty() {
+                        Some(Diagnostic::error(record.domain.span, "empty advertising system domain (field #1)"))
+                    } else if let Some(domain) = strip_domain_url(record.domain.value) {
+                        Some(
+                            Diagnostic::error(
+                                record.domain.span,
+                                "advertising system domain contains a scheme or a path",
+                            )
+                            .with_hint(format!("use the bare domain `{domain}`")),
+                        )
+                    } else if record.account_id.value.is_empty() {
+                        Some(Diagnostic::error(record.account_id.span, "empty account ID (field #2)"))
+                    } else {
+                        match record.relation.value {
+                            Relation::Unknown("") => {
+                                Some(Diagnostic::error(record.relation.span, "empty relation (field #3)"))
+                            }
+                            Relation::Unknown(x) => Some(
+                                Diagnostic::error(record.relation.span, format!("unknown relation `{x}`"))
+                                    .with_hint("expected `DIRECT` or `RESELLER`"),
+                            ),
+                            _ => interior_empty_field(data, record.relation.span.end..line.span.end),
+                        }
+                    }
+                }
+                SpannedLineData::Unknown(x) => Some(
+                    Diagnostic::warning(x.span, "unrecognized line")
+                        .with_hint("expected a record, a variable or a comment"),
+                ),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Reports the first empty field after field #3 that is followed by a non-empty field. `tail` is the range of the
+/// line after the relation.
+fn interior_empty_field(data: &str, tail: Range<usize>) -> Option<Diagnostic> {
+    let (body, _, _) = split_line(&data[tail.clone()]);
+    let mut start = tail.start;
+    let fields: Vec<_> = body
+        .split(',')
+        .map(|x| {
+            let field = (start, x);
+            start += x.len() + 1;
+            field
+        })
+        .skip(1)
+        .collect();
+
+    let last = fields.iter().rposition(|(_, x)| !x.trim().is_empty())?;
+    let (i, (start, x)) = fields[..last].iter().enumerate().find(|(_, (_, x))| x.trim().is_empty())?;
+    let start = start + x.len() - x.trim_start().len();
+    let message = match i {
+        0 => "empty certification authority ID (field #4) followed by other fields".to_string(),
+        _ => format!("empty field #{} followed by other fields", i + 4),
+    };
+    Some(Diagnostic::error(start..start, message))
+}
+
+#[test]
+fn test_diagnostic_location() {
+    let source = "abc\nあいう, x\n";
+    assert_eq!(Diagnostic::warning(0..1, "").location(source), (1, 1));
+    assert_eq!(Diagnostic::warning(4..7, "").location(source), (2, 1));
+    assert_eq!(Diagnostic::warning(15..16, "").location(source), (2, 6));
+    assert_eq!(Diagnostic::warning(100..100, "").location(source), (3, 1));
+}
+
+#[test]
+fn test_diagnostic_render() {
+    let source = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj, k, RESELER\r\n";
+    assert_eq!(
+        Diagnostic::error(24..31, "unknown relation `RESELER`").render(source),
+        "error: unknown relation `RESELER`\n  --> 10:7\n   |\n10 | j, k, RESELER\n   |       ^^^^^^^\n"
+    );
+    assert_eq!(
+        Diagnostic::warning(4..100, "multi-line span").with_hint("hint").render("a\nb\nc\nd"),
+        "warning: multi-line span\n --> 3:1\n  |\n3 | c\n  | ^\n  = help: hint\n"
+    );
+    assert_eq!(Diagnostic::warning(0..0, "empty").render(""), "warning: empty\n --> 1:1\n  |\n1 | \n  | ^\n");
+}
+
+#[test]
+fn test_diagnose_empty_fields() {
+    let source = ",f2,DIRECT\nf1,,DIRECT\nf1,f2,\nf1,f2,DIRECT,\nf1,f2,DIRECT, ,";
+    assert_eq!(AdsTxt::diagnose(source), vec![
+        Diagnostic::error(0..0, "empty advertising system domain (field #1)"),
+        Diagnostic::error(14..14, "empty account ID (field #2)"),
+        Diagnostic::error(28..28, "empty relation (field #3)"),
+    ]);
+
+    let source = "greenadexchange.com, 12345, DIRECT, , x
+f1, f2, DIRECT, f4,, x ; e
+f1, f2, DIRECT, f4, , # c";
+    assert_eq!(AdsTxt::diagnose(source), vec![
+        Diagnostic::error(36..36, "empty certification authority ID (field #4) followed by other fields"),
+        Diagnostic::error(59..59, "empty field #5 followed by other fields"),
+    ]);
+    assert_eq!(AdsTxt::parse(source).records[0].0.authority_id, None);
+}
+
+#[test]
+fn test_diagnose_domain_urls() {
+    let source = "https://f1.com/, f2, DIRECT\nf1.com/ads, f2, DIRCT\nf1.com, f2, DIRECT";
+    assert_eq!(AdsTxt::diagnose(source), vec![
+        Diagnostic::error(0..15, "advertising system domain contains a scheme or a path")
+            .with_hint("use the bare domain `f1.com`"),
+        Diagnostic::error(28..38, "advertising system domain contains a scheme or a path")
+            .with_hint("use the bare domain `f1.com`"),
+    ]);
+}
+
+#[test]
+fn test_diagnose() {
+    let source = "# comment\nf1, f2, DIRCT\nf1, f2, DIRECT\n\nunknown";
+    assert_eq!(AdsTxt::diagnose(source), vec![
+        Diagnostic::error(18..23, "unknown relation `DIRCT`").with_hint("expected `DIRECT` or `RESELLER`"),
+        Diagnostic::warning(40..47, "unrecognized line").with_hint("expected a record, a variable or a comment"),
+    ]);
+}
@@ -0,0 +1,133 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use rayon::prelude::*;
+
+use crate::{cow, AdsTxt};
+
+/// Iterator over the documents of a crawl output directory, parsed in parallel.
+///
+/// See also [`load_dir`].
+#[derive(Debug)]
+pub struct DirDocuments {
+    receiver: mpsc::IntoIter<(String, io::Result<cow::AdsTxt<'static>>)>,
+}
+
+impl Iterator for DirDocuments {
+    type Item = (String, io::Result<cow::AdsTxt<'static>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.next()
+    }
+}
+
+/// Parses every file under the directory `path` in parallel on the rayon thread pool.
+///
+/// Yields `(domain, ads.txt)` pairs as files are parsed, in no particular order. The domain is the file name
+/// without the `.txt` extension, e.g. `example.com.txt`, or the name of the parent directory for files named
+/// `ads.txt` and `app-ads.txt`, e.g. `example.com/ads.txt`. Hidden files and files without a `.txt` extension are
+/// skipped.
+///
+/// A file that cannot be read or is not valid UTF-8 yields an error for its domain and does not stop the others.
+/// Returns an error if the directory tree itself cannot be listed.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+/// # let dir = std::env::temp_dir().join(format!("adstxt-doctest-{}", std::process::id()));
+/// # std::fs::create_dir_all(dir.join("example.org")).unwrap();
+/// # std::fs::write(dir.join("example.com.txt"), "greenadexchange.com, 12345, DIRECT\n").unwrap();
+/// # std::fs::write(dir.join("example.org/ads.txt"), "blueadexchange.com, XF436, RESELLER\n").unwrap();
+///
+/// let mut documents: Vec<_> = load_dir(&dir).unwrap().map(|(domain, txt)| (domain, txt.unwrap())).collect();
+/// documents.sort_by(|a, b| a.0.cmp(&b.0));
+/// assert_eq!(documents[0].0, "example.com");
+/// assert_eq!(documents[1].0, "example.org");
+/// assert_eq!(documents[1].1.records[0].0.account_id, "XF436");
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn load_dir(path: impl AsRef<Path>) -> io::Result<DirDocuments> {
+    let mut files = Vec::new();
+    walk(path.as_ref(), &mut files)?;
+
+    let (sender, receiver) = mpsc::channel();
+    rayon::spawn(move || {
+        // Sending fails once the caller dropped the iterator, which stops loading the files not yet started.
+        let _ = files
+            .into_par_iter()
+            .try_for_each_with(sender, |sender, (domain, path)| sender.send((domain, load(&path))));
+    });
+    Ok(DirDocuments { receiver: receiver.into_iter() })
+}
+
+fn walk(dir: &Path, files: &mut Vec<(String, PathBuf)>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else { continue };
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            walk(&path, files)?;
+        } else if let Some(domain) = domain(&path, name) {
+            files.push((domain.to_owned(), path));
+        }
+    }
+    Ok(())
+}
+
+#[inline(always)]
+fn domain<'a>(path: &'a Path, name: &'a str) -> Option<&'a str> {
+    if name.eq_ignore_ascii_case("ads.txt") || name.eq_ignore_ascii_case("app-ads.txt") {
+        path.parent()?.file_name()?.to_str()
+    } else {
+        let stem = name.len().checked_sub(4).filter(|x| name.get(*x..).is_some_and(|x| x.eq_ignore_ascii_case(".txt")));
+        stem.map(|x| &name[..x]).filter(|x| !x.is_empty())
+    }
+}
+
+fn load(path: &Path) -> io::Result<cow::AdsTxt<'static>> {
+    let data = fs::read_to_string(path).map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+    Ok(cow::AdsTxt::from(AdsTxt::parse(&data)).into_owned())
+}
+
+#[test]
+fn test_domain() {
+    assert_eq!(domain(Path::new("crawl/example.com.txt"), "example.com.txt"), Some("example.com"));
+    assert_eq!(domain(Path::new("crawl/example.com.TXT"), "example.com.TXT"), Some("example.com"));
+    assert_eq!(domain(Path::new("crawl/example.com"), "example.com"), None);
+    assert_eq!(domain(Path::new("crawl/example.com.meta.json"), "example.com.meta.json"), None);
+    assert_eq!(domain(Path::new("crawl/index.html"), "index.html"), None);
+    assert_eq!(domain(Path::new("crawl/README"), "README"), None);
+    assert_eq!(domain(Path::new("crawl/.txt"), ".txt"), None);
+    assert_eq!(domain(Path::new("crawl/bücher"), "bücher"), None);
+    assert_eq!(domain(Path::new("crawl/example.com/ads.txt"), "ads.txt"), Some("example.com"));
+    assert_eq!(domain(Path::new("crawl/example.com/APP-ADS.TXT"), "APP-ADS.TXT"), Some("example.com"));
+}
+
+#[test]
+fn test_load_dir() {
+    let dir = std::env::temp_dir().join(format!("adstxt-test-dir-{}", std::process::id()));
+    fs::create_dir_all(dir.join("b.com")).unwrap();
+    fs::write(dir.join("a.com.txt"), "greenadexchange.com, 1, DIRECT\n").unwrap();
+    fs::write(dir.join("b.com/ads.txt"), "silverssp.com, 2, RESELLER\ncontact=x\n").unwrap();
+    fs::write(dir.join("c.com.txt"), b"\xff").unwrap();
+    fs::write(dir.join(".hidden"), "greenadexchange.com, 3, DIRECT\n").unwrap();
+    fs::write(dir.join("a.com.meta.json"), "{}").unwrap();
+    fs::write(dir.join("README"), "crawl output").unwrap();
+
+    let mut documents: Vec<_> = load_dir(&dir).unwrap().collect();
+    documents.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(documents.iter().map(|(x, _)| x.as_str()).collect::<Vec<_>>(), vec!["a.com", "b.com", "c.com"]);
+    assert_eq!(documents[0].1.as_ref().unwrap().as_borrowed(), AdsTxt::parse("greenadexchange.com, 1, DIRECT"));
+    assert_eq!(documents[1].1.as_ref().unwrap().variables.len(), 1);
+    assert_eq!(documents[2].1.as_ref().unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(load_dir(&dir).unwrap_err().kind(), io::ErrorKind::NotFound);
+}
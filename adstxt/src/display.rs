@@ -0,0 +1,179 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{AdsTxt, Comment, Extension, LineData, Record, Relation, Variable};
+
+impl fmt::Display for Relation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Writes the record with fields separated by `, `, e.g. `greenadexchange.com, 12345, DIRECT, d75815a79`.
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}, {}", self.domain, self.account_id, self.relation)?;
+        match self.authority_id {
+            Some(x) => write!(f, ", {x}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes the variable as `name=value`.
+impl fmt::Display for Variable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+impl fmt::Display for Comment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Display for Extension<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Writes the line without the line feed, e.g. `greenadexchange.com, 12345, DIRECT ; ext # comment`.
+impl fmt::Display for LineData<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tail = |f: &mut fmt::Formatter<'_>, extension: &Option<Extension<'_>>, comment: &Option<Comment<'_>>| {
+            if let Some(x) = extension {
+                write!(f, " ; {x}")?;
+            }
+            match comment {
+                Some(x) => write!(f, " {x}"),
+                None => Ok(()),
+            }
+        };
+
+        match self {
+            LineData::Comment(x) => write!(f, "{x}"),
+            LineData::Record { record, extension, comment } => {
+                write!(f, "{record}")?;
+                tail(f, extension, comment)
+            }
+            LineData::Variable { variable, extension, comment } => {
+                write!(f, "{variable}")?;
+                tail(f, extension, comment)
+            }
+            LineData::Empty => Ok(()),
+            LineData::Unknown(x) => f.write_str(x),
+        }
+    }
+}
+
+/// Writes the records followed by the variables, one per line.
+impl fmt::Display for AdsTxt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (record, extension) in &self.records {
+            writeln!(f, "{}", LineData::Record {
+                record: record.clone(),
+                extension: extension.clone(),
+                comment: None
+            })?;
+        }
+        for (variable, extension) in &self.variables {
+            writeln!(f, "{}", LineData::Variable {
+                variable: variable.clone(),
+                extension: extension.clone(),
+                comment: None
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl AdsTxt<'_> {
+    /// Returns the smallest equivalent ads.txt file.
+    ///
+    /// Comments, blank lines and unrecognized lines are dropped, no whitespace is written around separators, and
+    /// duplicated records (same [`RecordKey`](crate::RecordKey), authority ID and extension) and variables are
+    /// written only once. Records come first, then variables, both in their original order.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let adstxt = AdsTxt::parse(
+    ///     "# comment
+    /// greenadexchange.com, 12345, DIRECT, d75815a79 # comment
+    /// GreenAdExchange.com , 12345 , DIRECT , d75815a79
+    ///
+    /// contact = adops@example.com ; ext",
+    /// );
+    /// assert_eq!(adstxt.to_compact_string(), "greenadexchange.com,12345,DIRECT,d75815a79\ncontact=adops@example.com;ext\n");
+    /// ```
+    pub fn to_compact_string(&self) -> String {
+        let mut buf = String::new();
+
+        let mut seen = HashSet::new();
+        for (r, e) in &self.records {
+            if seen.insert((r.key(), r.authority_id, e.as_ref().map(|x| x.0))) {
+                buf.push_str(&format!("{},{},{}", r.domain, r.account_id, r.relation));
+                if let Some(x) = r.authority_id {
+                    buf.push_str(&format!(",{x}"));
+                }
+                push_compact_tail(&mut buf, e);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for (v, e) in &self.variables {
+            if seen.insert((v.name.to_ascii_lowercase(), v.value, e.as_ref().map(|x| x.0))) {
+                buf.push_str(&format!("{}={}", v.name, v.value));
+                push_compact_tail(&mut buf, e);
+            }
+        }
+
+        buf
+    }
+}
+
+#[inline(always)]
+fn push_compact_tail(buf: &mut String, extension: &Option<Extension<'_>>) {
+    if let Some(x) = extension {
+        buf.push(';');
+        buf.push_str(x.0);
+    }
+    buf.push('\n');
+}
+
+#[test]
+fn test_display() {
+    let data = "# comment
+greenadexchange.com, 12345, DIRECT, d75815a79 ; ext # comment
+blueadexchange.com, XF436, RESELLER
+contact=adops@example.com # comment
+
+unknown";
+    let lines = AdsTxt::parse_lines(data);
+    assert_eq!(lines.iter().map(|x| x.to_string()).collect::<Vec<_>>().join("\n"), data);
+    assert_eq!(
+        AdsTxt::parse(data).to_string(),
+        "greenadexchange.com, 12345, DIRECT, d75815a79 ; ext\nblueadexchange.com, XF436, RESELLER\ncontact=adops@example.com\n"
+    );
+}
+
+#[test]
+fn test_to_compact_string() {
+    let adstxt = AdsTxt::parse(
+        "a.com, 1, DIRECT
+contact=x
+a.com, 1, DIRECT, cert
+A.COM,1,DIRECT
+a.com, 1, DIRECT ; ext
+CONTACT=x
+contact=y",
+    );
+    assert_eq!(
+        adstxt.to_compact_string(),
+        "a.com,1,DIRECT\na.com,1,DIRECT,cert\na.com,1,DIRECT;ext\ncontact=x\ncontact=y\n"
+    );
+    assert!(AdsTxt::parse(&adstxt.to_compact_string()).semantically_eq(&adstxt));
+    assert_eq!(AdsTxt::parse("# only comment").to_compact_string(), "");
+}
@@ -0,0 +1,82 @@
+use crate::{Extension, LineData};
+
+/// Decodes typed data from the extension field of a record or variable.
+///
+/// Implement this for your own extension payloads instead of re-parsing [`Extension`]'s inner string at every
+/// call site.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Region<'a>(&'a str);
+///
+/// impl<'a> FromExtension<'a> for Region<'a> {
+///     type Error = &'static str;
+///
+///     fn from_extension(extension: &Extension<'a>) -> Result<Self, Self::Error> {
+///         extension.0.strip_prefix("region=").map(Region).ok_or("missing region")
+///     }
+/// }
+///
+/// let txt = AdsTxt::parse("greenadexchange.com, 12345, DIRECT ; region=eu\nblueadexchange.com, 4536, DIRECT ; x");
+/// let regions: Vec<_> = txt.records.iter().filter_map(|(_, x)| x.as_ref()).map(|x| x.decode::<Region>()).collect();
+/// assert_eq!(regions, vec![Ok(Region("eu")), Err("missing region")]);
+/// ```
+pub trait FromExtension<'a>: Sized {
+    /// The error returned when the extension data cannot be decoded.
+    type Error;
+
+    /// Decodes `extension`.
+    fn from_extension(extension: &Extension<'a>) -> Result<Self, Self::Error>;
+}
+
+impl<'a> Extension<'a> {
+    /// Decodes the extension data as `T`.
+    pub fn decode<T: FromExtension<'a>>(&self) -> Result<T, T::Error> {
+        T::from_extension(self)
+    }
+}
+
+impl<'a> LineData<'a> {
+    /// Decodes the extension data of a record or variable line as `T`.
+    ///
+    /// Returns `None` when the line has no extension data.
+    pub fn extension_as<T: FromExtension<'a>>(&self) -> Option<Result<T, T::Error>> {
+        match self {
+            LineData::Record { extension: Some(x), .. } | LineData::Variable { extension: Some(x), .. } => {
+                Some(x.decode())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_extension_as() {
+    #[derive(Debug, PartialEq)]
+    struct Hints<'a>(Vec<&'a str>);
+
+    impl<'a> FromExtension<'a> for Hints<'a> {
+        type Error = ();
+
+        fn from_extension(extension: &Extension<'a>) -> Result<Self, Self::Error> {
+            match extension.0 {
+                "" => Err(()),
+                x => Ok(Hints(x.split(',').map(str::trim).collect())),
+            }
+        }
+    }
+
+    let lines = crate::AdsTxt::parse_lines(
+        "greenadexchange.com, 12345, DIRECT ; a, b\ncontact=x ; c\nblueadexchange.com, 4536, DIRECT\n# ; d\nx=y ;",
+    );
+    assert_eq!(lines.iter().map(|x| x.extension_as::<Hints>()).collect::<Vec<_>>(), vec![
+        Some(Ok(Hints(vec!["a", "b"]))),
+        Some(Ok(Hints(vec!["c"]))),
+        None,
+        None,
+        Some(Err(()))
+    ]);
+}
@@ -0,0 +1,82 @@
+use std::{fs::File, io, path::Path, str};
+
+use memmap2::Mmap;
+
+use crate::AdsTxt;
+
+/// Represents an ads.txt file parsed in place from a memory map.
+///
+/// The records and variables borrow from the mapped file, so no copy of its contents is made. The parse result is
+/// available through [`AdsTxtFile::ads_txt`].
+///
+/// See also [`AdsTxt::parse_file`].
+#[derive(Debug)]
+pub struct AdsTxtFile {
+    // Declared before `mmap` so that it is dropped first.
+    txt: AdsTxt<'static>,
+    mmap: Mmap,
+}
+
+impl AdsTxt<'_> {
+    /// Memory-maps the file at `path` and parses it without copying its contents.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the file is not valid UTF-8.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while the returned [`AdsTxtFile`] is alive, see
+    /// [`memmap2::Mmap`]. Read the file into a `String` and use [`AdsTxt::parse`] if this cannot be guaranteed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    /// # let path = std::env::temp_dir().join(format!("adstxt-doctest-{}.txt", std::process::id()));
+    /// # std::fs::write(&path, "greenadexchange.com, 12345, DIRECT\n").unwrap();
+    ///
+    /// let file = unsafe { AdsTxt::parse_file(&path) }.unwrap();
+    /// assert_eq!(file.ads_txt().records[0].0.domain, "greenadexchange.com");
+    /// # drop(file);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub unsafe fn parse_file(path: impl AsRef<Path>) -> io::Result<AdsTxtFile> {
+        let mmap = Mmap::map(&File::open(path)?)?;
+        let data = str::from_utf8(&mmap).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        // SAFETY: the mapped memory does not move when `mmap` is moved, and outlives `txt` as it is dropped after it.
+        // `txt` is only handed out with the lifetime of a borrow of `self`.
+        let data: &'static str = &*(data as *const str);
+        Ok(AdsTxtFile { txt: AdsTxt::parse(data), mmap })
+    }
+}
+
+impl AdsTxtFile {
+    /// Returns the parse result, borrowing from the mapped file.
+    pub fn ads_txt(&self) -> &AdsTxt<'_> {
+        &self.txt
+    }
+
+    /// Returns the contents of the mapped file.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the contents were validated as UTF-8 when the file was parsed.
+        unsafe { str::from_utf8_unchecked(&self.mmap) }
+    }
+}
+
+#[test]
+fn test_parse_file() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("adstxt-test-{}.txt", std::process::id()));
+    let data = "greenadexchange.com, 12345, DIRECT ; ext\ncontact=adops@example.com\n";
+    std::fs::write(&path, data).unwrap();
+    let file = unsafe { AdsTxt::parse_file(&path) }.unwrap();
+    assert_eq!(file.as_str(), data);
+    assert_eq!(file.ads_txt(), &AdsTxt::parse(data));
+    drop(file);
+
+    std::fs::write(&path, "").unwrap();
+    assert_eq!(unsafe { AdsTxt::parse_file(&path) }.unwrap().ads_txt(), &AdsTxt::parse(""));
+
+    std::fs::write(&path, b"greenadexchange.com, \xff, DIRECT").unwrap();
+    assert_eq!(unsafe { AdsTxt::parse_file(&path) }.unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(unsafe { AdsTxt::parse_file(&path) }.unwrap_err().kind(), io::ErrorKind::NotFound);
+}
@@ -0,0 +1,92 @@
+use crate::AdsTxt;
+
+impl AdsTxt<'_> {
+    /// Returns a stable hash of the normalized contents of the file.
+    ///
+    /// The hash is computed over the set of records and variables, so it does not change with comments, blank
+    /// lines, whitespace around fields, the order of lines or duplicated lines. Advertising system domains and
+    /// variable names are compared ASCII case-insensitively, without the other conversions of
+    /// [`canonical_domain`](crate::canonical_domain) that depend on cargo features. The algorithm (64-bit FNV-1a)
+    /// is fixed, so fingerprints can be stored and compared across runs, crate versions and feature sets.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let a = AdsTxt::parse("# v1\ngreenadexchange.com, 12345, DIRECT\ncontact=adops@example.com");
+    /// let b = AdsTxt::parse("CONTACT = adops@example.com\nGreenAdExchange.com,12345,DIRECT # v2");
+    /// let c = AdsTxt::parse("greenadexchange.com, 12345, RESELLER\ncontact=adops@example.com");
+    ///
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// assert_ne!(a.fingerprint(), c.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let records = self.records.iter().map(|(r, e)| {
+            [
+                "r",
+                &r.domain.to_ascii_lowercase(),
+                r.account_id,
+                r.relation.as_str(),
+                r.authority_id.unwrap_or(""),
+                e.as_ref().map_or("", |e| e.0),
+            ]
+            .join("\0")
+        });
+        let variables = self
+            .variables
+            .iter()
+            .map(|(v, e)| ["v", &v.name.to_ascii_lowercase(), v.value, e.as_ref().map_or("", |e| e.0)].join("\0"));
+
+        let mut entries: Vec<_> = records.chain(variables).collect();
+        entries.sort_unstable();
+        entries.dedup();
+
+        entries.iter().fold(FNV_OFFSET_BASIS, |hash, entry| fnv1a(fnv1a(hash, entry.as_bytes()), b"\n"))
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[inline(always)]
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &x| (hash ^ x as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[test]
+fn test_fnv1a() {
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), 0xcbf29ce484222325);
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"foobar"), 0x85944171f73967e8);
+}
+
+#[test]
+fn test_fingerprint() {
+    let base = AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79\nblueadexchange.com, XF436, DIRECT");
+    assert_eq!(
+        base.fingerprint(),
+        AdsTxt::parse("blueadexchange.com, XF436, DIRECT\n\n  greenadexchange.com,12345,DIRECT,d75815a79  \nblueadexchange.com, XF436, DIRECT").fingerprint()
+    );
+    assert_ne!(base.fingerprint(), AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79").fingerprint());
+    assert_ne!(
+        base.fingerprint(),
+        AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nblueadexchange.com, XF436, DIRECT").fingerprint()
+    );
+    assert_ne!(
+        base.fingerprint(),
+        AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79\nblueadexchange.com, xf436, DIRECT").fingerprint()
+    );
+    assert_ne!(
+        base.fingerprint(),
+        AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79 ; ext\nblueadexchange.com, XF436, DIRECT")
+            .fingerprint()
+    );
+    assert_eq!(AdsTxt::parse("").fingerprint(), AdsTxt::parse("# comment").fingerprint());
+}
+
+#[test]
+fn test_fingerprint_is_stable() {
+    // Independent of cargo features such as `idna`.
+    let txt = AdsTxt::parse("Bücher.example, 12345, DIRECT\nwww.greenadexchange.com, 1, RESELLER\ncontact=x");
+    assert_eq!(txt.fingerprint(), 13741045678560241242);
+}
@@ -0,0 +1,112 @@
+//! Tricky ads.txt samples with their expected parse output, for testing tools built on this crate.
+//!
+//! The samples reproduce patterns found in crawled files, with anonymized domains and account IDs: byte order
+//! marks and CRLF line endings, HTML error pages served as ads.txt, ads.txt 1.1 variables, large network-managed
+//! files and common syntax mistakes. Each [`Fixture`] pairs a sample with the [`Display`](std::fmt::Display)
+//! output of [`AdsTxt::parse`](crate::AdsTxt::parse) for it, i.e. the records followed by the variables, one per line.
+//!
+//! # Example
+//! ```rust
+//! # use adstxt::*;
+//!
+//! for fixture in fixtures::FIXTURES {
+//!     assert_eq!(AdsTxt::parse(fixture.input).to_string(), fixture.expected, "{}", fixture.name);
+//! }
+//! assert_eq!(fixtures::get("bom_crlf").unwrap().expected.lines().count(), 4);
+//! ```
+
+#[cfg(test)]
+use crate::AdsTxt;
+
+/// Represents a sample ads.txt and its expected parse output.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Fixture {
+    /// File name of the sample without extension.
+    pub name: &'static str,
+    /// What the sample exercises.
+    pub description: &'static str,
+    /// Contents of the sample.
+    pub input: &'static str,
+    /// Records followed by variables as written by the `Display` implementation of [`AdsTxt`](crate::AdsTxt).
+    pub expected: &'static str,
+}
+
+macro_rules! fixture {
+    ($name:literal, $description:literal) => {
+        Fixture {
+            name: $name,
+            description: $description,
+            input: include_str!(concat!("../fixtures/", $name, ".txt")),
+            expected: include_str!(concat!("../fixtures/", $name, ".expected")),
+        }
+    };
+}
+
+/// Every bundled sample.
+pub const FIXTURES: &[Fixture] = &[
+    fixture!("bom_crlf", "UTF-8 byte order mark, CRLF line endings, tabs and padding around fields"),
+    fixture!("html_error_page", "HTML 404 page served with a successful status instead of ads.txt"),
+    fixture!("ads_txt_1_1_variables", "ads.txt 1.1 variables: OWNERDOMAIN, MANAGERDOMAIN, SUBDOMAIN, ..."),
+    fixture!("network_reseller", "network-managed file with many resellers, extension data and duplicates"),
+    fixture!("common_mistakes", "URLs and quotes in fields, lowercase relations, missing fields, other separators"),
+    fixture!("mojibake", "mis-decoded text, replacement characters and internationalized domains"),
+];
+
+/// Returns the sample named `name`.
+pub fn get(name: &str) -> Option<&'static Fixture> {
+    FIXTURES.iter().find(|x| x.name == name)
+}
+
+/// Returns a network-managed ads.txt with `records` records over a handful of advertising systems, for
+/// benchmarks and tests of large inputs that are not worth bundling.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// let data = fixtures::large_network(10_000);
+/// assert_eq!(AdsTxt::parse(&data).records.len(), 10_000);
+/// ```
+pub fn large_network(records: usize) -> String {
+    const EXCHANGES: [(&str, Option<&str>); 5] = [
+        ("greenadexchange.com", Some("d75815a79")),
+        ("blueadexchange.com", Some("f08c47fec0942fa0")),
+        ("silverssp.com", None),
+        ("redbidder.net", None),
+        ("orangeads.io", Some("7842df1d2fe2db34")),
+    ];
+
+    let mut buf = String::from("# Network-managed ads.txt\nownerdomain=network.example\n");
+    for i in 0..records {
+        if i % 100 == 0 {
+            buf.push_str(&format!("\n# --- partner {} ---\n", i / 100 + 1));
+        }
+        let (exchange, authority_id) = EXCHANGES[i % EXCHANGES.len()];
+        let relation = if i % 7 == 0 { "DIRECT" } else { "RESELLER" };
+        buf.push_str(&format!("{exchange}, {}, {relation}", 10_000 + i));
+        if let Some(x) = authority_id {
+            buf.push_str(&format!(", {x}"));
+        }
+        buf.push('\n');
+    }
+    buf
+}
+
+#[test]
+fn test_fixtures() {
+    for fixture in FIXTURES {
+        assert_eq!(AdsTxt::parse(fixture.input).to_string(), fixture.expected, "{}", fixture.name);
+    }
+    assert_eq!(get("bom_crlf").map(|x| x.name), Some("bom_crlf"));
+    assert_eq!(get("unknown"), None);
+}
+
+#[test]
+fn test_large_network() {
+    let data = large_network(250);
+    let txt = AdsTxt::parse(&data);
+    assert_eq!(txt.records.len(), 250);
+    assert_eq!(txt.variables.len(), 1);
+    assert_eq!(txt.records[249].0.account_id, "10249");
+    assert_eq!(large_network(0), "# Network-managed ads.txt\nownerdomain=network.example\n");
+}
@@ -0,0 +1,236 @@
+//! Publisher to advertising system graph built from many ads.txt files.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{canonical_domain, AdsTxt, Relation};
+
+/// Represents an edge of [`SupplyGraph`]: a publisher authorizing an account of an advertising system.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Edge<'a> {
+    /// Domain the ads.txt was fetched from.
+    pub publisher: &'a str,
+    /// FIELD #1: Domain name of the advertising system.
+    pub exchange: &'a str,
+    /// FIELD #2: Publisher's Account ID.
+    pub account_id: &'a str,
+    /// FIELD #3: Type of Account/Relationship.
+    pub relation: Relation<'a>,
+}
+
+/// Represents the supply graph between publishers and advertising systems.
+///
+/// Domains are compared by their [`canonical_domain`]. Methods returning or rendering domains keep the first spelling
+/// of each domain in insertion order.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{graph::SupplyGraph, AdsTxt};
+///
+/// let graph: SupplyGraph = vec![
+///     ("example.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT")),
+///     ("example.org", AdsTxt::parse("greenadexchange.com, 678, RESELLER")),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(graph.edges_to("greenadexchange.com").count(), 2);
+/// assert_eq!(graph.publishers(), vec!["example.com", "example.org"]);
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SupplyGraph<'a> {
+    edges: Vec<Edge<'a>>,
+}
+
+impl<'a> SupplyGraph<'a> {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the records of the ads.txt fetched from `publisher`.
+    pub fn add(&mut self, publisher: &'a str, adstxt: &AdsTxt<'a>) {
+        self.edges.extend(adstxt.records.iter().map(|(r, _)| Edge {
+            publisher,
+            exchange: r.domain,
+            account_id: r.account_id,
+            relation: r.relation.clone(),
+        }));
+    }
+
+    /// Returns all edges in insertion order.
+    pub fn edges(&self) -> &[Edge<'a>] {
+        &self.edges
+    }
+
+    /// Returns the publisher domains, deduplicated and sorted by their canonical domain.
+    pub fn publishers(&self) -> Vec<&'a str> {
+        first_spellings(self.edges.iter().map(|x| x.publisher)).into_values().collect()
+    }
+
+    /// Returns the advertising system domains, deduplicated and sorted by their canonical domain.
+    pub fn exchanges(&self) -> Vec<&'a str> {
+        first_spellings(self.edges.iter().map(|x| x.exchange)).into_values().collect()
+    }
+
+    /// Returns the edges going out of `publisher`.
+    pub fn edges_from<'s>(&'s self, publisher: &str) -> impl Iterator<Item = &'s Edge<'a>> {
+        let publisher = canonical_domain(publisher).into_owned();
+        self.edges.iter().filter(move |x| canonical_domain(x.publisher) == publisher)
+    }
+
+    /// Returns the edges going into `exchange`.
+    pub fn edges_to<'s>(&'s self, exchange: &str) -> impl Iterator<Item = &'s Edge<'a>> {
+        let exchange = canonical_domain(exchange).into_owned();
+        self.edges.iter().filter(move |x| canonical_domain(x.exchange) == exchange)
+    }
+
+    /// Returns the advertising system domains that are only ever listed with the `RESELLER` relation, as ordered by
+    /// [`SupplyGraph::exchanges`].
+    pub fn reseller_only_exchanges(&self) -> Vec<&'a str> {
+        self.exchanges()
+            .into_iter()
+            .filter(|exchange| self.edges_to(exchange).all(|x| x.relation == Relation::Reseller))
+            .collect()
+    }
+
+    /// Renders the graph in the [DOT] language.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot(&self) -> String {
+        let nodes = self.nodes();
+        let mut buf = String::from("digraph supply {\n");
+        for x in &self.edges {
+            buf.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{} {}\"];\n",
+                escape_dot(nodes[&canonical_domain(x.publisher)]),
+                escape_dot(nodes[&canonical_domain(x.exchange)]),
+                escape_dot(x.account_id),
+                escape_dot(x.relation.as_str()),
+            ));
+        }
+        buf.push_str("}\n");
+        buf
+    }
+
+    /// Renders the graph in the [GraphML] format.
+    ///
+    /// [GraphML]: http://graphml.graphdrawing.org/
+    pub fn to_graphml(&self) -> String {
+        let mut buf = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"account_id\" for=\"edge\" attr.name=\"account_id\" attr.type=\"string\"/>\n",
+            "  <key id=\"relation\" for=\"edge\" attr.name=\"relation\" attr.type=\"string\"/>\n",
+            "  <graph id=\"supply\" edgedefault=\"directed\">\n",
+        ));
+        let nodes = self.nodes();
+        for x in nodes.values() {
+            buf.push_str(&format!("    <node id=\"{}\"/>\n", escape_xml(x)));
+        }
+        for x in &self.edges {
+            buf.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\"><data key=\"account_id\">{}</data><data key=\"relation\">{}</data></edge>\n",
+                escape_xml(nodes[&canonical_domain(x.publisher)]),
+                escape_xml(nodes[&canonical_domain(x.exchange)]),
+                escape_xml(x.account_id),
+                escape_xml(x.relation.as_str()),
+            ));
+        }
+        buf.push_str("  </graph>\n</graphml>\n");
+        buf
+    }
+
+    /// Returns the first spelling of the publisher and advertising system domains, keyed by their canonical domain.
+    fn nodes(&self) -> BTreeMap<Cow<'a, str>, &'a str> {
+        first_spellings(self.edges.iter().flat_map(|x| [x.publisher, x.exchange]))
+    }
+}
+
+impl<'a> FromIterator<(&'a str, AdsTxt<'a>)> for SupplyGraph<'a> {
+    fn from_iter<T: IntoIterator<Item = (&'a str, AdsTxt<'a>)>>(iter: T) -> Self {
+        let mut graph = SupplyGraph::new();
+        for (publisher, adstxt) in iter {
+            graph.add(publisher, &adstxt);
+        }
+        graph
+    }
+}
+
+fn first_spellings<'a>(iter: impl Iterator<Item = &'a str>) -> BTreeMap<Cow<'a, str>, &'a str> {
+    let mut spellings = BTreeMap::new();
+    for x in iter {
+        spellings.entry(canonical_domain(x)).or_insert(x);
+    }
+    spellings
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+fn test_graph() -> SupplyGraph<'static> {
+    vec![
+        ("example.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nblueadexchange.com, XF436, RESELLER")),
+        ("example.org", AdsTxt::parse("GreenAdExchange.com, 678, RESELLER\nblueadexchange.com, 9, RESELLER")),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn test_supply_graph_queries() {
+    let graph = test_graph();
+    assert_eq!(graph.edges().len(), 4);
+    assert_eq!(graph.publishers(), vec!["example.com", "example.org"]);
+    assert_eq!(graph.exchanges(), vec!["blueadexchange.com", "greenadexchange.com"]);
+    assert_eq!(graph.edges_from("Example.com").map(|x| x.account_id).collect::<Vec<_>>(), vec!["12345", "XF436"]);
+    assert_eq!(graph.edges_to("greenadexchange.com").map(|x| x.account_id).collect::<Vec<_>>(), vec!["12345", "678"]);
+    assert_eq!(graph.edges_from("www.Example.com.").count(), 2);
+    assert_eq!(graph.edges_to("www.GreenAdExchange.com.").count(), 2);
+    assert_eq!(graph.reseller_only_exchanges(), vec!["blueadexchange.com"]);
+}
+
+#[test]
+fn test_supply_graph_mixed_case() {
+    let graph: SupplyGraph = vec![
+        ("Example.com", AdsTxt::parse("GreenAdExchange.com, 1, RESELLER")),
+        ("example.com", AdsTxt::parse("greenadexchange.com, 2, RESELLER\nwww.greenadexchange.com., 3, RESELLER")),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(graph.publishers(), vec!["Example.com"]);
+    assert_eq!(graph.exchanges(), vec!["GreenAdExchange.com"]);
+    assert_eq!(graph.reseller_only_exchanges(), vec!["GreenAdExchange.com"]);
+
+    let graphml = graph.to_graphml();
+    assert_eq!(graphml.matches("<node ").count(), 2);
+    assert_eq!(graphml.matches("<edge source=\"Example.com\" target=\"GreenAdExchange.com\">").count(), 3);
+    assert_eq!(graph.to_dot().matches("\"Example.com\" -> \"GreenAdExchange.com\"").count(), 3);
+}
+
+#[test]
+fn test_supply_graph_to_dot() {
+    let graph: SupplyGraph =
+        vec![("example.com", AdsTxt::parse("greenadexchange.com, \"1\", DIRECT"))].into_iter().collect();
+    assert_eq!(
+        graph.to_dot(),
+        "digraph supply {\n    \"example.com\" -> \"greenadexchange.com\" [label=\"\\\"1\\\" DIRECT\"];\n}\n"
+    );
+}
+
+#[test]
+fn test_supply_graph_to_graphml() {
+    let graph: SupplyGraph =
+        vec![("example.com", AdsTxt::parse("greenadexchange.com, <1>, DIRECT"))].into_iter().collect();
+    let graphml = graph.to_graphml();
+    assert!(graphml.contains("    <node id=\"example.com\"/>\n    <node id=\"greenadexchange.com\"/>\n"));
+    assert!(graphml.contains(
+        "<edge source=\"example.com\" target=\"greenadexchange.com\"><data key=\"account_id\">&lt;1&gt;</data><data key=\"relation\">DIRECT</data></edge>"
+    ));
+    assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+}
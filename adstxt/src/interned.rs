@@ -0,0 +1,200 @@
+use std::{collections::HashMap, ops::Range, sync::Arc};
+
+use crate::{parser::parse_relation, AdsTxt, Extension, Record, Variable};
+
+/// Represents an owned collection of many parsed ads.txt files, storing each distinct string once.
+///
+/// Exchange domains, relations and account IDs repeat across the files of a crawl, so a corpus takes a fraction
+/// of the memory of the owned documents. Strings are referred to by 32-bit indices; [`AdsTxtCorpus::get`] and
+/// [`AdsTxtCorpus::iter`] give back [`AdsTxt`] values borrowing from the corpus.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// let dump = "#=== example.com ===
+/// greenadexchange.com, 12345, DIRECT
+/// #=== example.org ===
+/// greenadexchange.com, 12345, DIRECT
+/// contact=adops@example.org";
+///
+/// let mut corpus = AdsTxtCorpus::new();
+/// corpus.extend(AdsTxt::parse_corpus(dump, CorpusDelimiter::default()));
+/// assert_eq!(corpus.len(), 2);
+/// assert_eq!(corpus.strings_len(), 7);
+///
+/// let (domain, txt) = corpus.get(1).unwrap();
+/// assert_eq!(domain, "example.org");
+/// assert_eq!(txt, AdsTxt::parse("greenadexchange.com, 12345, DIRECT\ncontact=adops@example.org"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AdsTxtCorpus {
+    pub(crate) strings: Vec<Arc<str>>,
+    pub(crate) symbols: HashMap<Arc<str>, Symbol>,
+    pub(crate) documents: Vec<Document>,
+    pub(crate) records: Vec<(InternedRecord, Option<Symbol>)>,
+    pub(crate) variables: Vec<(InternedVariable, Option<Symbol>)>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub(crate) struct Symbol(pub(crate) u32);
+
+#[derive(Debug, Clone)]
+pub(crate) struct Document {
+    pub(crate) domain: Symbol,
+    pub(crate) records: Range<usize>,
+    pub(crate) variables: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InternedRecord {
+    pub(crate) domain: Symbol,
+    pub(crate) account_id: Symbol,
+    pub(crate) relation: Symbol,
+    pub(crate) authority_id: Option<Symbol>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InternedVariable {
+    pub(crate) name: Symbol,
+    pub(crate) value: Symbol,
+}
+
+impl AdsTxtCorpus {
+    /// Returns an empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the ads.txt of `domain`, copying the strings that are not yet in the corpus.
+    ///
+    /// # Panics
+    /// Panics if the corpus would hold more than `u32::MAX` distinct strings.
+    pub fn push(&mut self, domain: &str, txt: &AdsTxt<'_>) {
+        let domain = self.intern(domain);
+
+        let start = self.records.len();
+        for (r, e) in &txt.records {
+            let record = InternedRecord {
+                domain: self.intern(r.domain),
+                account_id: self.intern(r.account_id),
+                relation: self.intern(r.relation.as_str()),
+                authority_id: r.authority_id.map(|x| self.intern(x)),
+            };
+            let extension = e.as_ref().map(|x| self.intern(x.0));
+            self.records.push((record, extension));
+        }
+        let records = start..self.records.len();
+
+        let start = self.variables.len();
+        for (v, e) in &txt.variables {
+            let variable = InternedVariable { name: self.intern(v.name), value: self.intern(v.value) };
+            let extension = e.as_ref().map(|x| self.intern(x.0));
+            self.variables.push((variable, extension));
+        }
+        let variables = start..self.variables.len();
+
+        self.documents.push(Document { domain, records, variables });
+    }
+
+    /// Returns the domain and the ads.txt of the `index`-th document, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<(&str, AdsTxt<'_>)> {
+        self.documents.get(index).map(|x| self.document(x))
+    }
+
+    /// Returns an iterator over the domains and the ads.txt of the documents, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, AdsTxt<'_>)> + '_ {
+        self.documents.iter().map(|x| self.document(x))
+    }
+
+    /// Returns the number of documents.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Returns `true` if the corpus contains no documents.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Returns the number of distinct strings stored.
+    pub fn strings_len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub(crate) fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(x) = self.symbols.get(s) {
+            return *x;
+        }
+        let symbol = Symbol(u32::try_from(self.strings.len()).expect("too many distinct strings"));
+        let s: Arc<str> = s.into();
+        self.strings.push(s.clone());
+        self.symbols.insert(s, symbol);
+        symbol
+    }
+
+    #[inline(always)]
+    pub(crate) fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+
+    #[inline(always)]
+    pub(crate) fn record(&self, record: &InternedRecord) -> Record<'_> {
+        Record {
+            domain: self.resolve(record.domain),
+            account_id: self.resolve(record.account_id),
+            relation: parse_relation(self.resolve(record.relation)),
+            authority_id: record.authority_id.map(|x| self.resolve(x)),
+        }
+    }
+
+    pub(crate) fn document(&self, document: &Document) -> (&str, AdsTxt<'_>) {
+        let records = self.records[document.records.clone()]
+            .iter()
+            .map(|(r, e)| (self.record(r), e.map(|x| Extension(self.resolve(x)))))
+            .collect();
+        let variables = self.variables[document.variables.clone()]
+            .iter()
+            .map(|(v, e)| {
+                let variable = Variable { name: self.resolve(v.name), value: self.resolve(v.value) };
+                (variable, e.map(|x| Extension(self.resolve(x))))
+            })
+            .collect();
+        (self.resolve(document.domain), AdsTxt { records, variables })
+    }
+}
+
+impl<'d, 'a> Extend<(&'d str, AdsTxt<'a>)> for AdsTxtCorpus {
+    fn extend<T: IntoIterator<Item = (&'d str, AdsTxt<'a>)>>(&mut self, iter: T) {
+        for (domain, txt) in iter {
+            self.push(domain, &txt);
+        }
+    }
+}
+
+impl<'d, 'a> FromIterator<(&'d str, AdsTxt<'a>)> for AdsTxtCorpus {
+    fn from_iter<T: IntoIterator<Item = (&'d str, AdsTxt<'a>)>>(iter: T) -> Self {
+        let mut corpus = AdsTxtCorpus::new();
+        corpus.extend(iter);
+        corpus
+    }
+}
+
+#[test]
+fn test_corpus_interning() {
+    let a = AdsTxt::parse("greenadexchange.com, 1, DIRECT, d75815a79 ; ext\nsilverssp.com, 2, other\ncontact=x");
+    let b = AdsTxt::parse("greenadexchange.com, 1, direct\nsilverssp.com, 3, RESELLER ; ext");
+    let corpus: AdsTxtCorpus =
+        vec![("a.com", a.clone()), ("b.com", b.clone()), ("c.com", AdsTxt::parse(""))].into_iter().collect();
+
+    assert_eq!(corpus.len(), 3);
+    assert_eq!(corpus.get(0), Some(("a.com", a)));
+    assert_eq!(corpus.get(1), Some(("b.com", b)));
+    assert_eq!(corpus.get(2), Some(("c.com", AdsTxt::parse(""))));
+    assert_eq!(corpus.get(3), None);
+    assert_eq!(corpus.iter().map(|(x, _)| x).collect::<Vec<_>>(), vec!["a.com", "b.com", "c.com"]);
+
+    // a.com greenadexchange.com 1 DIRECT d75815a79 ext silverssp.com 2 other contact x b.com direct 3 RESELLER c.com
+    assert_eq!(corpus.strings_len(), 16);
+    assert!(AdsTxtCorpus::new().is_empty());
+}
@@ -0,0 +1,84 @@
+use std::{slice, vec};
+
+use crate::{AdsTxt, Extension, Record, Relation, Variable};
+
+impl<'a> AdsTxt<'a> {
+    /// Returns an iterator over the records and their extension data.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let txt = AdsTxt::parse(
+    ///     "greenadexchange.com, 12345, DIRECT
+    /// silverssp.com, 9675, RESELLER
+    /// contact=adops@example.com",
+    /// );
+    /// assert_eq!(txt.iter().map(|(r, _)| r.domain).collect::<Vec<_>>(), vec!["greenadexchange.com", "silverssp.com"]);
+    /// assert_eq!(txt.iter_resellers().map(|(r, _)| r.account_id).collect::<Vec<_>>(), vec!["9675"]);
+    /// assert_eq!(txt.iter_variables().count(), 1);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, (Record<'a>, Option<Extension<'a>>)> {
+        self.records.iter()
+    }
+
+    /// Returns an iterator over the variables and their extension data.
+    pub fn iter_variables(&self) -> slice::Iter<'_, (Variable<'a>, Option<Extension<'a>>)> {
+        self.variables.iter()
+    }
+
+    /// Returns an iterator over the `DIRECT` records and their extension data.
+    pub fn iter_direct(&self) -> impl Iterator<Item = &(Record<'a>, Option<Extension<'a>>)> + '_ {
+        self.iter().filter(|(r, _)| r.relation == Relation::Direct)
+    }
+
+    /// Returns an iterator over the `RESELLER` records and their extension data.
+    pub fn iter_resellers(&self) -> impl Iterator<Item = &(Record<'a>, Option<Extension<'a>>)> + '_ {
+        self.iter().filter(|(r, _)| r.relation == Relation::Reseller)
+    }
+}
+
+impl<'a> IntoIterator for AdsTxt<'a> {
+    type Item = (Record<'a>, Option<Extension<'a>>);
+    type IntoIter = vec::IntoIter<Self::Item>;
+
+    /// Consumes the ads.txt, returning its records. The variables are dropped.
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s AdsTxt<'a> {
+    type Item = &'s (Record<'a>, Option<Extension<'a>>);
+    type IntoIter = slice::Iter<'s, (Record<'a>, Option<Extension<'a>>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn test_iter() {
+    let txt = AdsTxt::parse(
+        "a.com, 1, DIRECT
+b.com, 2, RESELLER ; ext
+c.com, 3, other
+d.com, 4, DIRECT
+contact=adops@example.com",
+    );
+
+    assert_eq!(txt.iter().map(|(r, _)| r.domain).collect::<Vec<_>>(), vec!["a.com", "b.com", "c.com", "d.com"]);
+    assert_eq!(txt.iter_direct().map(|(r, _)| r.domain).collect::<Vec<_>>(), vec!["a.com", "d.com"]);
+    assert_eq!(txt.iter_resellers().map(|(r, _)| r.domain).collect::<Vec<_>>(), vec!["b.com"]);
+    assert_eq!(txt.iter_variables().map(|(v, _)| v.name).collect::<Vec<_>>(), vec!["contact"]);
+
+    let mut n = 0;
+    for (record, _) in &txt {
+        assert!(record.domain.ends_with(".com"));
+        n += 1;
+    }
+    assert_eq!(n, 4);
+
+    let records: Vec<_> = txt.clone().into_iter().collect();
+    assert_eq!(records, txt.records);
+}
@@ -0,0 +1,169 @@
+use std::borrow::Cow;
+
+use crate::{Record, Relation};
+
+/// Represents the identity of a record: canonical advertising system domain, account ID and relation.
+///
+/// The domain is canonicalized with [`canonical_domain`] so that keys can be compared, hashed and sorted across
+/// documents without normalizing the fields again. The account ID is kept as is because it may be case sensitive.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+/// use std::collections::HashSet;
+///
+/// let a = AdsTxt::parse("GreenAdExchange.com, 12345, DIRECT\nblueadexchange.com, XF436, DIRECT");
+/// let b = AdsTxt::parse("greenadexchange.com, 12345, DIRECT");
+///
+/// let a: HashSet<_> = a.records.iter().map(|(r, _)| r.key()).collect();
+/// let b: HashSet<_> = b.records.iter().map(|(r, _)| r.key()).collect();
+/// assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![&RecordKey::new("blueadexchange.com", "XF436", Relation::Direct)]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordKey<'a> {
+    /// Canonical FIELD #1.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub domain: Cow<'a, str>,
+    /// FIELD #2.
+    pub account_id: &'a str,
+    /// FIELD #3.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub relation: Relation<'a>,
+}
+
+impl<'a> RecordKey<'a> {
+    /// Creates a key, canonicalizing `domain`.
+    pub fn new(domain: &'a str, account_id: &'a str, relation: Relation<'a>) -> Self {
+        RecordKey { domain: canonical_domain(domain), account_id, relation }
+    }
+}
+
+impl<'a> Record<'a> {
+    /// Returns the [`RecordKey`] of the record.
+    pub fn key(&self) -> RecordKey<'a> {
+        RecordKey::new(self.domain, self.account_id, self.relation.clone())
+    }
+}
+
+impl<'a> From<&Record<'a>> for RecordKey<'a> {
+    fn from(record: &Record<'a>) -> Self {
+        record.key()
+    }
+}
+
+/// Returns the canonical form of an advertising system domain, used wherever the crate matches domains.
+///
+/// Surrounding whitespace, trailing dots and a leading `www.` label are removed, and the domain is lowercased.
+/// With the `idna` feature, internationalized domains are converted to punycode, so that `bücher.example` and
+/// `xn--bcher-kva.example` match; otherwise they are only lowercased. Borrows from `domain` when no conversion is
+/// needed.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// assert_eq!(canonical_domain(" WWW.GreenAdExchange.com. "), "greenadexchange.com");
+/// assert_eq!(canonical_domain("www.com"), "www.com");
+/// ```
+pub fn canonical_domain(domain: &str) -> Cow<'_, str> {
+    let domain = strip_domain(domain);
+    if !domain.is_ascii() {
+        Cow::Owned(to_ascii(domain))
+    } else if domain.bytes().any(|x| x.is_ascii_uppercase()) {
+        Cow::Owned(domain.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(domain)
+    }
+}
+
+/// Returns `true` if the canonical form of `domain` is `canonical`, without allocating for ASCII domains.
+pub(crate) fn is_canonical_domain(domain: &str, canonical: &str) -> bool {
+    let domain = strip_domain(domain);
+    if domain.is_ascii() {
+        domain.len() == canonical.len()
+            && domain.bytes().zip(canonical.bytes()).all(|(a, b)| a.to_ascii_lowercase() == b)
+    } else {
+        to_ascii(domain) == canonical
+    }
+}
+
+/// Removes surrounding whitespace, trailing dots and a leading `www.` label.
+#[inline(always)]
+fn strip_domain(domain: &str) -> &str {
+    let domain = domain.trim().trim_end_matches('.');
+    match domain.get(..4) {
+        Some(x) if x.eq_ignore_ascii_case("www.") && domain[4..].contains('.') => &domain[4..],
+        _ => domain,
+    }
+}
+
+#[cfg(feature = "idna")]
+#[inline(always)]
+fn to_ascii(domain: &str) -> String {
+    idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase())
+}
+
+#[cfg(not(feature = "idna"))]
+#[inline(always)]
+fn to_ascii(domain: &str) -> String {
+    domain.to_lowercase()
+}
+
+#[test]
+fn test_canonical_domain() {
+    assert!(matches!(canonical_domain("greenadexchange.com"), Cow::Borrowed("greenadexchange.com")));
+    assert!(matches!(canonical_domain(" greenadexchange.com.. "), Cow::Borrowed("greenadexchange.com")));
+    assert!(matches!(canonical_domain("www.greenadexchange.com"), Cow::Borrowed("greenadexchange.com")));
+    assert_eq!(canonical_domain("WWW.GreenAdExchange.COM"), "greenadexchange.com");
+    assert_eq!(canonical_domain("www.www.example.com"), "www.example.com");
+    assert_eq!(canonical_domain("wwwexample.com"), "wwwexample.com");
+    assert_eq!(canonical_domain("www."), "www");
+    assert_eq!(canonical_domain(""), "");
+    #[cfg(feature = "idna")]
+    assert_eq!(canonical_domain("Bücher.example"), "xn--bcher-kva.example");
+    #[cfg(not(feature = "idna"))]
+    assert_eq!(canonical_domain("Bücher.example"), "bücher.example");
+}
+
+#[test]
+fn test_is_canonical_domain() {
+    for domain in ["greenadexchange.com", " WWW.GreenAdExchange.com. ", "www.www.example.com", "Bücher.example", ""] {
+        assert!(is_canonical_domain(domain, &canonical_domain(domain)), "{domain}");
+    }
+    assert!(!is_canonical_domain("GreenAdExchange.com", "GreenAdExchange.com"));
+    assert!(!is_canonical_domain("www.com", "com"));
+}
+
+#[test]
+fn test_record_key() {
+    let record =
+        Record { domain: "GreenAdExchange.com", account_id: "XF436", relation: Relation::Direct, authority_id: None };
+    assert_eq!(record.key(), RecordKey {
+        domain: Cow::Borrowed("greenadexchange.com"),
+        account_id: "XF436",
+        relation: Relation::Direct
+    });
+    assert_eq!(RecordKey::from(&record), RecordKey::new("greenadexchange.com", "XF436", Relation::Direct));
+    assert_ne!(record.key(), RecordKey::new("greenadexchange.com", "xf436", Relation::Direct));
+    assert_ne!(record.key(), RecordKey::new("greenadexchange.com", "XF436", Relation::Reseller));
+    assert!(matches!(RecordKey::new("a.com", "1", Relation::Direct).domain, Cow::Borrowed(_)));
+    assert_eq!(RecordKey::new("www.A.com.", "1", Relation::Direct), RecordKey::new("a.com", "1", Relation::Direct));
+}
+
+#[test]
+fn test_record_key_ord() {
+    let mut keys = vec![
+        RecordKey::new("b.com", "1", Relation::Direct),
+        RecordKey::new("a.com", "2", Relation::Reseller),
+        RecordKey::new("a.com", "2", Relation::Direct),
+        RecordKey::new("A.com", "1", Relation::Direct),
+    ];
+    keys.sort();
+    assert_eq!(keys, vec![
+        RecordKey::new("a.com", "1", Relation::Direct),
+        RecordKey::new("a.com", "2", Relation::Direct),
+        RecordKey::new("a.com", "2", Relation::Reseller),
+        RecordKey::new("b.com", "1", Relation::Direct),
+    ]);
+}
@@ -0,0 +1,388 @@
+use crate::{
+    parser::{parse_record_tail, parse_relation},
+    span::offset,
+    AdsTxt, Comment, Diagnostic, Extension, LineData, Record, Relation, Variable,
+};
+
+/// Represents the options of the lenient parser.
+///
+/// Every option is disabled by default, in which case the lenient parser behaves exactly like
+/// [`AdsTxt::parse_lines`]. Each recovery performed by an enabled option is reported as a [`Diagnostic`].
+///
+/// See also [`AdsTxt::parse_lines_with`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Recognize lines starting with `//` and separator lines such as `====` or `----` as comments.
+    pub alternative_comments: bool,
+    /// Extract records whose fields are separated by spaces or tabs instead of commas, such as
+    /// `greenadexchange.com\t12345\tDIRECT`. The recovery is only attempted on lines that are not otherwise
+    /// recognized, and requires a `DIRECT` or `RESELLER` relation.
+    pub whitespace_delimited: bool,
+    /// Strip double quotes wrapping record fields and variable values, as in spreadsheet exports such as
+    /// `"greenadexchange.com", "12345", "DIRECT"`.
+    pub strip_quotes: bool,
+    /// Strip the scheme and the path of advertising system domains written as URLs, such as
+    /// `https://greenadexchange.com/`.
+    pub strip_domain_urls: bool,
+}
+
+impl ParseOptions {
+    /// Returns options with every recovery enabled.
+    pub fn lenient() -> Self {
+        ParseOptions {
+            alternative_comments: true,
+            whitespace_delimited: true,
+            strip_quotes: true,
+            strip_domain_urls: true,
+        }
+    }
+}
+
+impl AdsTxt<'_> {
+    /// Parse the contents of ads.txt, recovering from common mistakes according to `options`.
+    ///
+    /// See also [`AdsTxt::parse_lines_with`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let (adstxt, diagnostics) = AdsTxt::parse_with("// comment\ngreenadexchange.com, 12345, DIRECT", &ParseOptions::lenient());
+    /// assert_eq!(adstxt.records.len(), 1);
+    /// assert_eq!(diagnostics.len(), 1);
+    /// ```
+    pub fn parse_with<'a>(data: &'a str, options: &ParseOptions) -> (AdsTxt<'a>, Vec<Diagnostic>) {
+        let (lines, diagnostics) = AdsTxt::parse_lines_with(data, options);
+        let (records, variables) = lines.into_iter().fold((Vec::new(), Vec::new()), |mut acc, x| match x {
+            LineData::Record { record, extension, .. } => {
+                acc.0.push((record, extension));
+                acc
+            }
+            LineData::Variable { variable, extension, .. } => {
+                acc.1.push((variable, extension));
+                acc
+            }
+            _ => acc,
+        });
+
+        (AdsTxt { records, variables }, diagnostics)
+    }
+
+    /// Parse the contents of ads.txt into [`LineData`], recovering from common mistakes according to `options`.
+    ///
+    /// Returns the line data together with a warning for every line that needed a recovery.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let options = ParseOptions { alternative_comments: true, ..Default::default() };
+    /// let (lines, diagnostics) = AdsTxt::parse_lines_with("// comment\n=====", &options);
+    /// assert_eq!(lines, vec![LineData::Comment(Comment("// comment")), LineData::Comment(Comment("====="))]);
+    /// assert_eq!(diagnostics[0].span, 0..10);
+    /// ```
+    pub fn parse_lines_with<'a>(data: &'a str, options: &ParseOptions) -> (Vec<LineData<'a>>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let lines = data.split('\n').map(|x| parse_line(data, x, options, &mut diagnostics)).collect();
+        (lines, diagnostics)
+    }
+}
+
+#[inline(always)]
+fn parse_line<'a>(
+    data: &'a str,
+    line: &'a str,
+    options: &ParseOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> LineData<'a> {
+    let trimmed = line.trim();
+
+    if options.alternative_comments && is_alternative_comment(trimmed) {
+        diagnostics.push(
+            Diagnostic::warning(offset(data, trimmed), "non-standard comment syntax")
+                .with_hint("start comments with `#`"),
+        );
+        return LineData::Comment(Comment(trimmed));
+    }
+
+    let line_data = match LineData::parse(line) {
+        LineData::Unknown(x) if options.whitespace_delimited => match parse_whitespace_delimited_record(x) {
+            Some((record, extension, comment)) => {
+                diagnostics.push(
+                    Diagnostic::warning(offset(data, x), "record fields are not separated by commas")
+                        .with_hint("separate fields with `,`"),
+                );
+                LineData::Record { record, extension, comment }
+            }
+            None => LineData::Unknown(x),
+        },
+        x => x,
+    };
+
+    let line_data = match line_data {
+        LineData::Record { record, extension, comment } if options.strip_quotes => {
+            let (record, stripped) = strip_record_quotes(record);
+            if stripped {
+                diagnostics.push(
+                    Diagnostic::warning(offset(data, trimmed), "record fields are wrapped in double quotes")
+                        .with_hint("remove the quotes"),
+                );
+            }
+            LineData::Record { record, extension, comment }
+        }
+        LineData::Variable { variable, extension, comment } if options.strip_quotes => {
+            let (value, stripped) = strip_quotes(variable.value);
+            if stripped {
+                diagnostics.push(
+                    Diagnostic::warning(offset(data, trimmed), "variable value is wrapped in double quotes")
+                        .with_hint("remove the quotes"),
+                );
+            }
+            LineData::Variable { variable: Variable { value, ..variable }, extension, comment }
+        }
+        x => x,
+    };
+
+    match line_data {
+        LineData::Record { record, extension, comment } if options.strip_domain_urls => {
+            match strip_domain_url(record.domain) {
+                Some(domain) => {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            offset(data, record.domain),
+                            "advertising system domain is written as a URL",
+                        )
+                        .with_hint(format!("use the bare domain `{domain}`")),
+                    );
+                    LineData::Record { record: Record { domain, ..record }, extension, comment }
+                }
+                None => LineData::Record { record, extension, comment },
+            }
+        }
+        x => x,
+    }
+}
+
+/// Returns the host of `domain` if it is written as a URL, i.e. with a scheme or a path.
+#[inline(always)]
+pub(crate) fn strip_domain_url(domain: &'_ str) -> Option<&'_ str> {
+    let host = domain.split_once("://").map_or(domain, |(_, x)| x);
+    let host = host.split(['/', '?']).next().unwrap_or_default();
+    if host.len() == domain.len() || host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+#[test]
+fn test_strip_domain_url() {
+    assert_eq!(strip_domain_url("https://greenadexchange.com/"), Some("greenadexchange.com"));
+    assert_eq!(strip_domain_url("http://greenadexchange.com"), Some("greenadexchange.com"));
+    assert_eq!(strip_domain_url("greenadexchange.com/ads"), Some("greenadexchange.com"));
+    assert_eq!(strip_domain_url("greenadexchange.com?x=1"), Some("greenadexchange.com"));
+    assert_eq!(strip_domain_url("greenadexchange.com"), None);
+    assert_eq!(strip_domain_url("https://"), None);
+    assert_eq!(strip_domain_url("/ads"), None);
+}
+
+#[test]
+fn test_parse_lines_with_strip_domain_urls() {
+    let data = "https://greenadexchange.com/, 12345, DIRECT\nblueadexchange.com/ads, XF436, RESELLER\nf1, f2, DIRECT";
+    let options = ParseOptions { strip_domain_urls: true, ..Default::default() };
+
+    let (lines, diagnostics) = AdsTxt::parse_lines_with(data, &options);
+    let domains: Vec<_> = lines
+        .iter()
+        .map(|x| match x {
+            LineData::Record { record, .. } => record.domain,
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(domains, vec!["greenadexchange.com", "blueadexchange.com", "f1"]);
+    assert_eq!(diagnostics, vec![
+        Diagnostic::warning(0..28, "advertising system domain is written as a URL")
+            .with_hint("use the bare domain `greenadexchange.com`"),
+        Diagnostic::warning(44..66, "advertising system domain is written as a URL")
+            .with_hint("use the bare domain `blueadexchange.com`"),
+    ]);
+}
+
+#[inline(always)]
+fn strip_quotes(field: &'_ str) -> (&'_ str, bool) {
+    match field.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+        Some(x) => (x.trim(), true),
+        None => (field, false),
+    }
+}
+
+#[inline(always)]
+fn strip_record_quotes(record: Record<'_>) -> (Record<'_>, bool) {
+    let (domain, a) = strip_quotes(record.domain);
+    let (account_id, b) = strip_quotes(record.account_id);
+    let (relation, c) = match record.relation {
+        Relation::Unknown(x) => match strip_quotes(x) {
+            (x, true) => (parse_relation(x), true),
+            (_, false) => (Relation::Unknown(x), false),
+        },
+        x => (x, false),
+    };
+    let (authority_id, d) = match record.authority_id.map(strip_quotes) {
+        Some((x, stripped)) => (Some(x), stripped),
+        None => (None, false),
+    };
+    (Record { domain, account_id, relation, authority_id }, a || b || c || d)
+}
+
+#[test]
+fn test_strip_quotes() {
+    assert_eq!(strip_quotes("\"example.com\""), ("example.com", true));
+    assert_eq!(strip_quotes("\" example.com \""), ("example.com", true));
+    assert_eq!(strip_quotes("\"\""), ("", true));
+    assert_eq!(strip_quotes("\""), ("\"", false));
+    assert_eq!(strip_quotes("\"example.com"), ("\"example.com", false));
+    assert_eq!(strip_quotes("example.com"), ("example.com", false));
+}
+
+#[test]
+fn test_parse_lines_with_strip_quotes() {
+    let data = "\"greenadexchange.com\", \"12345\", \"DIRECT\", \"d75815a79\"\ncontact=\"adops@example.com\"\nf1, f2, RESELLER";
+    let options = ParseOptions { strip_quotes: true, ..Default::default() };
+
+    let (lines, diagnostics) = AdsTxt::parse_lines_with(data, &options);
+    assert_eq!(lines, vec![
+        LineData::Record {
+            record: Record {
+                domain: "greenadexchange.com",
+                account_id: "12345",
+                relation: Relation::Direct,
+                authority_id: Some("d75815a79")
+            },
+            extension: None,
+            comment: None
+        },
+        LineData::Variable {
+            variable: Variable { name: "contact", value: "adops@example.com" },
+            extension: None,
+            comment: None
+        },
+        AdsTxt::parse_lines(data)[2].clone(),
+    ]);
+    assert_eq!(diagnostics, vec![
+        Diagnostic::warning(0..53, "record fields are wrapped in double quotes").with_hint("remove the quotes"),
+        Diagnostic::warning(54..81, "variable value is wrapped in double quotes").with_hint("remove the quotes"),
+    ]);
+}
+
+#[inline(always)]
+fn parse_whitespace_delimited_record(
+    line: &'_ str,
+) -> Option<(Record<'_>, Option<Extension<'_>>, Option<Comment<'_>>)> {
+    let (fields, tail) = match line.find([';', '#']) {
+        Some(i) => line.split_at(i),
+        None => (line, ""),
+    };
+
+    let mut iter = fields.split_whitespace();
+    let domain = iter.next()?;
+    let account_id = iter.next()?;
+    let relation = parse_relation(iter.next()?);
+    let authority_id = iter.next();
+    if iter.next().is_some() || matches!(relation, Relation::Unknown(_)) {
+        return None;
+    }
+
+    let (extension, comment) = parse_record_tail(tail).unwrap_or((None, None));
+    Some((Record { domain, account_id, relation, authority_id }, extension, comment))
+}
+
+#[test]
+fn test_parse_whitespace_delimited_record() {
+    assert_eq!(
+        parse_whitespace_delimited_record("greenadexchange.com\t12345  DIRECT"),
+        Some((
+            Record {
+                domain: "greenadexchange.com",
+                account_id: "12345",
+                relation: Relation::Direct,
+                authority_id: None
+            },
+            None,
+            None
+        ))
+    );
+    assert_eq!(
+        parse_whitespace_delimited_record("f1 f2 RESELLER f4 ; ext # comment"),
+        Some((
+            Record { domain: "f1", account_id: "f2", relation: Relation::Reseller, authority_id: Some("f4") },
+            Some(Extension("ext")),
+            Some(Comment("# comment"))
+        ))
+    );
+    assert_eq!(parse_whitespace_delimited_record("f1 f2 OTHER"), None);
+    assert_eq!(parse_whitespace_delimited_record("f1 f2"), None);
+    assert_eq!(parse_whitespace_delimited_record("f1 f2 DIRECT f4 f5"), None);
+    assert_eq!(parse_whitespace_delimited_record("unknown"), None);
+}
+
+#[test]
+fn test_parse_lines_with_whitespace_delimited() {
+    let data = "greenadexchange.com 12345 DIRECT\nsome text here\nf1, f2, DIRECT";
+    let options = ParseOptions { whitespace_delimited: true, ..Default::default() };
+
+    let (lines, diagnostics) = AdsTxt::parse_lines_with(data, &options);
+    assert_eq!(lines, vec![
+        LineData::Record {
+            record: Record {
+                domain: "greenadexchange.com",
+                account_id: "12345",
+                relation: Relation::Direct,
+                authority_id: None
+            },
+            extension: None,
+            comment: None
+        },
+        LineData::Unknown("some text here"),
+        AdsTxt::parse_lines(data)[2].clone(),
+    ]);
+    assert_eq!(diagnostics, vec![
+        Diagnostic::warning(0..32, "record fields are not separated by commas").with_hint("separate fields with `,`")
+    ]);
+}
+
+#[inline(always)]
+fn is_alternative_comment(line: &str) -> bool {
+    line.starts_with("//")
+        || (line.len() >= 3 && ['=', '-', '*', '_'].into_iter().any(|c| line.chars().all(|x| x == c)))
+}
+
+#[test]
+fn test_is_alternative_comment() {
+    assert!(is_alternative_comment("// comment"));
+    assert!(is_alternative_comment("//"));
+    assert!(is_alternative_comment("==="));
+    assert!(is_alternative_comment("-----"));
+    assert!(is_alternative_comment("*****"));
+    assert!(is_alternative_comment("___"));
+    assert!(!is_alternative_comment("=="));
+    assert!(!is_alternative_comment("=-="));
+    assert!(!is_alternative_comment("/ comment"));
+    assert!(!is_alternative_comment("name=value"));
+}
+
+#[test]
+fn test_parse_lines_with_alternative_comments() {
+    let data = "// a, b, DIRECT\n  ====  \ngreenadexchange.com, 12345, DIRECT";
+
+    let (lines, diagnostics) = AdsTxt::parse_lines_with(data, &ParseOptions::default());
+    assert_eq!(lines, AdsTxt::parse_lines(data));
+    assert!(diagnostics.is_empty());
+
+    let (lines, diagnostics) = AdsTxt::parse_lines_with(data, &ParseOptions::lenient());
+    assert_eq!(lines[..2], [LineData::Comment(Comment("// a, b, DIRECT")), LineData::Comment(Comment("===="))]);
+    assert_eq!(lines[2], AdsTxt::parse_lines(data)[2]);
+    assert_eq!(diagnostics, vec![
+        Diagnostic::warning(0..15, "non-standard comment syntax").with_hint("start comments with `#`"),
+        Diagnostic::warning(18..22, "non-standard comment syntax").with_hint("start comments with `#`"),
+    ]);
+}
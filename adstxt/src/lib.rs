@@ -1,6 +1,80 @@
 //! This library provides a parser for [ads.txt v1.0.2].
 //!
 //! [ads.txt v1.0.2]: https://iabtechlab.com/wp-content/uploads/2019/03/IAB-OpenRTB-Ads.txt-Public-Spec-1.0.2.pdf
+//!
+//! # Features
+//!
+//! - `arrow`: enables `AdsTxt::to_arrow` and `AdsTxt::variables_to_arrow`, converting to Arrow record batches.
+//! - `avro`: enables the `avro` module, writing records as Avro object container files.
+//! - `fixtures`: enables the `fixtures` module, bundling tricky ads.txt samples with their expected parse output.
+//! - `idna`: converts internationalized domains to punycode in `canonical_domain`.
+//! - `mmap`: enables `AdsTxt::parse_file`, parsing a memory-mapped file in place.
+//! - `msgpack`: enables `AdsTxt::to_msgpack` and zero-copy `AdsTxt::from_msgpack`.
+//! - `protobuf`: enables the `proto` module, converting to and from prost messages.
+//! - `rayon`: enables `load_dir`, parsing the files of a crawl output directory in parallel.
+//! - `serde`: implements `Serialize` for the parsed data and reports, and zero-copy `Deserialize` for the parsed
+//!   data. Deserializing borrowed types requires a deserializer that can lend strings from its input; use the
+//!   types of the [`cow`] module otherwise.
+//! - `url`: enables the validated `Domain` type for FIELD #1 and domain-valued variables, `Variable::contact_url`,
+//!   and the `validate::invalid_domains` rule.
+//! - `yaml`: enables `AdsTxt::to_yaml`.
 
+pub mod analysis;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "avro")]
+pub mod avro;
+mod body;
+mod compare;
+mod corpus;
+pub mod cow;
+mod diagnostic;
+#[cfg(feature = "rayon")]
+mod dir;
+mod display;
+mod extension;
+#[cfg(feature = "mmap")]
+mod file;
+mod fingerprint;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod graph;
+mod interned;
+mod iter;
+mod key;
+mod lenient;
+mod lookup;
+#[cfg(feature = "msgpack")]
+mod msgpack;
 mod parser;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+mod set;
+mod source;
+mod span;
+pub mod sql;
+#[cfg(feature = "url")]
+mod typed;
+pub mod validate;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+pub use body::*;
+pub use compare::*;
+pub use corpus::*;
+pub use diagnostic::*;
+#[cfg(feature = "rayon")]
+pub use dir::*;
+pub use extension::*;
+#[cfg(feature = "mmap")]
+pub use file::*;
+pub use interned::*;
+pub use key::*;
+pub use lenient::*;
+pub use lookup::*;
 pub use parser::*;
+pub use set::*;
+pub use source::*;
+pub use span::*;
+#[cfg(feature = "url")]
+pub use typed::*;
@@ -0,0 +1,258 @@
+use std::{collections::HashSet, ops::Deref, sync::OnceLock};
+
+use crate::{canonical_domain, key::is_canonical_domain, AdsTxt, Extension, Record, RecordKey, Relation};
+
+impl<'a> AdsTxt<'a> {
+    /// Returns the records whose advertising system is `domain`, and their extension data.
+    ///
+    /// Domains are compared by their [`canonical_domain`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let txt = AdsTxt::parse(
+    ///     "RubiconProject.com, 1001, DIRECT
+    /// greenadexchange.com, 12345, DIRECT
+    /// rubiconproject.com, 1002, RESELLER
+    /// rubiconproject.com, 1001, RESELLER",
+    /// );
+    /// assert_eq!(txt.records_for_domain("rubiconproject.com").count(), 3);
+    /// assert_eq!(txt.accounts_for("rubiconproject.com.").collect::<Vec<_>>(), vec!["1001", "1002"]);
+    /// ```
+    pub fn records_for_domain<'s>(
+        &'s self,
+        domain: &'s str,
+    ) -> impl Iterator<Item = &'s (Record<'a>, Option<Extension<'a>>)> + 's {
+        let domain = canonical_domain(domain);
+        self.iter().filter(move |(r, _)| is_canonical_domain(r.domain, &domain))
+    }
+
+    /// Returns the distinct account IDs of the records whose advertising system is `exchange`, in order of
+    /// appearance.
+    ///
+    /// Domains are matched as in [`AdsTxt::records_for_domain`].
+    pub fn accounts_for<'s>(&'s self, exchange: &'s str) -> impl Iterator<Item = &'a str> + 's {
+        let mut seen = HashSet::new();
+        self.records_for_domain(exchange).map(|(r, _)| r.account_id).filter(move |x| seen.insert(*x))
+    }
+
+    /// Returns `true` if a record has the identity `key`.
+    ///
+    /// This scans the records without allocating. To test many keys against the same ads.txt in constant time, wrap
+    /// it in an [`IndexedAdsTxt`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let txt = AdsTxt::parse("GreenAdExchange.com, 12345, DIRECT");
+    /// assert!(txt.contains(&RecordKey::new("greenadexchange.com", "12345", Relation::Direct)));
+    /// assert!(!txt.contains(&RecordKey::new("greenadexchange.com", "12345", Relation::Reseller)));
+    /// ```
+    pub fn contains(&self, key: &RecordKey<'_>) -> bool {
+        self.records.iter().any(|(r, _)| {
+            r.account_id == key.account_id && r.relation == key.relation && is_canonical_domain(r.domain, &key.domain)
+        })
+    }
+
+    /// Returns whether the seller `account_id` of the advertising system `exchange` is authorized to sell the
+    /// inventory, and with which relations.
+    ///
+    /// Domains are matched as in [`AdsTxt::records_for_domain`]; account IDs are compared exactly. Fetching the
+    /// publisher's ads.txt, following redirects and `subdomain` or `ownerdomain` declarations, is left to the
+    /// caller.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let txt = AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nGreenAdExchange.com, 12345, RESELLER");
+    /// let authorization = txt.authorization_for("greenadexchange.com", "12345");
+    /// assert_eq!(authorization, Authorization { direct: true, reseller: true });
+    /// assert!(!txt.authorization_for("greenadexchange.com", "67890").is_authorized());
+    /// ```
+    pub fn authorization_for(&self, exchange: &str, account_id: &str) -> Authorization {
+        self.records_for_domain(exchange).filter(|(r, _)| r.account_id == account_id).fold(
+            Authorization::default(),
+            |x, (r, _)| Authorization {
+                direct: x.direct || r.relation == Relation::Direct,
+                reseller: x.reseller || r.relation == Relation::Reseller,
+            },
+        )
+    }
+
+    /// Returns the set of the [`RecordKey`]s of the records, for constant time membership tests.
+    pub fn record_set(&self) -> RecordSet<'a> {
+        self.records.iter().map(|(r, _)| r.key()).collect()
+    }
+}
+
+/// Represents the relations with which a seller is authorized by an ads.txt.
+///
+/// See also [`AdsTxt::authorization_for`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Authorization {
+    /// A `DIRECT` record lists the seller.
+    pub direct: bool,
+    /// A `RESELLER` record lists the seller.
+    pub reseller: bool,
+}
+
+impl Authorization {
+    /// Returns `true` if the seller is listed with a `DIRECT` or `RESELLER` relation.
+    pub fn is_authorized(&self) -> bool {
+        self.direct || self.reseller
+    }
+}
+
+/// Represents the set of the [`RecordKey`]s of an ads.txt, e.g. to authorize bid requests against it.
+///
+/// See also [`AdsTxt::record_set`].
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// let set = AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nsilverssp.com, 9675, RESELLER").record_set();
+/// assert_eq!(set.len(), 2);
+/// assert!(set.contains(&RecordKey::new("SilverSSP.com", "9675", Relation::Reseller)));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RecordSet<'a> {
+    keys: HashSet<RecordKey<'a>>,
+}
+
+impl RecordSet<'_> {
+    /// Returns `true` if the set contains `key`.
+    pub fn contains(&self, key: &RecordKey<'_>) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Returns the number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the set contains no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<'a> FromIterator<RecordKey<'a>> for RecordSet<'a> {
+    fn from_iter<T: IntoIterator<Item = RecordKey<'a>>>(iter: T) -> Self {
+        RecordSet { keys: iter.into_iter().collect() }
+    }
+}
+
+/// Represents an ads.txt with an index of its [`RecordKey`]s, built on the first membership test.
+///
+/// Dereferences to the wrapped [`AdsTxt`], which cannot be modified so that the index stays valid. Use it to
+/// authorize many bid requests against the same ads.txt.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// let txt = IndexedAdsTxt::new(AdsTxt::parse("greenadexchange.com, 12345, DIRECT\nsilverssp.com, 9675, RESELLER"));
+/// assert!(txt.contains(&RecordKey::new("SilverSSP.com", "9675", Relation::Reseller)));
+/// assert!(!txt.contains(&RecordKey::new("silverssp.com", "9675", Relation::Direct)));
+/// assert_eq!(txt.records.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedAdsTxt<'a> {
+    txt: AdsTxt<'a>,
+    set: OnceLock<RecordSet<'a>>,
+}
+
+impl<'a> IndexedAdsTxt<'a> {
+    /// Wraps `txt`. The index is built lazily.
+    pub fn new(txt: AdsTxt<'a>) -> Self {
+        IndexedAdsTxt { txt, set: OnceLock::new() }
+    }
+
+    /// Returns `true` if a record has the identity `key`, in constant time once the index is built.
+    pub fn contains(&self, key: &RecordKey<'_>) -> bool {
+        self.record_set().contains(key)
+    }
+
+    /// Returns the index, building it on the first call.
+    pub fn record_set(&self) -> &RecordSet<'a> {
+        self.set.get_or_init(|| self.txt.record_set())
+    }
+
+    /// Returns the wrapped ads.txt, dropping the index.
+    pub fn into_inner(self) -> AdsTxt<'a> {
+        self.txt
+    }
+}
+
+impl<'a> Deref for IndexedAdsTxt<'a> {
+    type Target = AdsTxt<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txt
+    }
+}
+
+impl<'a> From<AdsTxt<'a>> for IndexedAdsTxt<'a> {
+    fn from(txt: AdsTxt<'a>) -> Self {
+        IndexedAdsTxt::new(txt)
+    }
+}
+
+#[test]
+fn test_records_for_domain() {
+    let txt = AdsTxt::parse(
+        "a.com, 1, DIRECT
+A.COM., 2, RESELLER ; ext
+b.com, 1, DIRECT
+a.com, 1, RESELLER
+ba.com, 3, DIRECT",
+    );
+    assert_eq!(txt.records_for_domain(" a.com ").map(|(r, _)| r.account_id).collect::<Vec<_>>(), vec!["1", "2", "1"]);
+    assert_eq!(txt.accounts_for("A.com").collect::<Vec<_>>(), vec!["1", "2"]);
+    assert_eq!(txt.accounts_for("c.com").count(), 0);
+    assert_eq!(txt.accounts_for("WWW.a.com.").count(), 2);
+}
+
+#[test]
+fn test_authorization_for() {
+    let txt = AdsTxt::parse(
+        "a.com, 1, DIRECT\nA.COM., 2, RESELLER\nb.com, 1, RESELLER\nc.com, 3, other\nc.com, 4, DIRECT ; x",
+    );
+    assert_eq!(txt.authorization_for("a.com", "1"), Authorization { direct: true, reseller: false });
+    assert_eq!(txt.authorization_for("a.com", "2"), Authorization { direct: false, reseller: true });
+    assert!(!txt.authorization_for("a.com", "3").is_authorized());
+    assert!(!txt.authorization_for("c.com", "3").is_authorized());
+    assert!(txt.authorization_for("c.com", "4").is_authorized());
+    assert!(!txt.authorization_for("d.com", "1").is_authorized());
+}
+
+#[test]
+fn test_contains() {
+    use crate::Relation;
+
+    let txt = AdsTxt::parse("A.com, 1, DIRECT\nb.com, 2, RESELLER\nb.com, 2, RESELLER ; ext\nc.com, 3, other");
+    let set = txt.record_set();
+    let indexed = IndexedAdsTxt::from(txt.clone());
+    assert_eq!(set.len(), 3);
+    for (key, expected) in [
+        (RecordKey::new("a.com", "1", Relation::Direct), true),
+        (RecordKey::new("A.COM", "1", Relation::Direct), true),
+        (RecordKey::new("b.com", "2", Relation::Reseller), true),
+        (RecordKey::new("c.com", "3", Relation::Unknown("other")), true),
+        (RecordKey::new("a.com", "1", Relation::Reseller), false),
+        (RecordKey::new("b.com", "2 ", Relation::Reseller), false),
+        (RecordKey::new("d.com", "1", Relation::Direct), false),
+    ] {
+        assert_eq!(txt.contains(&key), expected, "{key:?}");
+        assert_eq!(set.contains(&key), expected, "{key:?}");
+        assert_eq!(indexed.contains(&key), expected, "{key:?}");
+    }
+    assert!(AdsTxt::parse("").record_set().is_empty());
+    assert_eq!(indexed.record_set(), &set);
+    assert_eq!(indexed.into_inner(), txt);
+}
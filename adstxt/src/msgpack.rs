@@ -0,0 +1,37 @@
+use crate::AdsTxt;
+
+impl<'a> AdsTxt<'a> {
+    /// Serializes the parsed contents of ads.txt as compact MessagePack, where structs are encoded as arrays.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let txt = AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79\ncontact=adops@example.com");
+    /// let bytes = txt.to_msgpack().unwrap();
+    /// assert_eq!(AdsTxt::from_msgpack(&bytes).unwrap(), txt);
+    /// ```
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec(self)
+    }
+
+    /// Deserializes MessagePack written by [`AdsTxt::to_msgpack`]. The strings are borrowed from `bytes`.
+    pub fn from_msgpack(bytes: &'a [u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+#[test]
+fn test_msgpack() {
+    let txt = AdsTxt::parse(
+        "greenadexchange.com, 1, DIRECT, d75815a79 ; ext
+blueadexchange.com, 2, RESELLER # comment
+silverssp.com, 3, other
+contact=adops@example.com ; note",
+    );
+    let bytes = txt.to_msgpack().unwrap();
+    assert_eq!(AdsTxt::from_msgpack(&bytes).unwrap(), txt);
+
+    assert!(AdsTxt::from_msgpack(&bytes[..bytes.len() - 1]).is_err());
+    assert_eq!(AdsTxt::from_msgpack(&AdsTxt::parse("").to_msgpack().unwrap()).unwrap(), AdsTxt::parse(""));
+}
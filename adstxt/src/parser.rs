@@ -1,9 +1,22 @@
+use std::ops::Range;
+
+use winnow::{
+    combinator::{opt, preceded},
+    token::{rest, take_till},
+    Parser,
+};
+
+use crate::canonical_domain;
+
 /// Represents [`ads.txt`][`ads.txt`] data.
 ///
 /// [`ads.txt`]: https://iabtechlab.com/ads-txt/
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdsTxt<'a> {
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub records: Vec<(Record<'a>, Option<Extension<'a>>)>,
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub variables: Vec<(Variable<'a>, Option<Extension<'a>>)>,
 }
 
@@ -96,6 +109,164 @@ impl AdsTxt<'_> {
     pub fn parse_lines(data: &'_ str) -> Vec<LineData<'_>> {
         data.split('\n').map(|x| LineData::parse(x)).collect()
     }
+
+    /// Re-parse part of the line data returned by [`AdsTxt::parse_lines`] after an edit.
+    ///
+    /// The lines in `range` are replaced with the lines of `data`, which may contain a different number of lines
+    /// than `range`. Only the new lines are parsed, the other entries are left untouched, since the meaning of
+    /// a line never depends on its neighbours. Returns the range of the replaced entries in the updated `lines`.
+    ///
+    /// # Panics
+    /// Panics if `range` is out of bounds of `lines`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let mut lines = AdsTxt::parse_lines("# comment\ngreenadexchange.com, 12345, DIRECT\ncontact=adops@example.com");
+    /// assert_eq!(AdsTxt::reparse_lines(&mut lines, 1..2, "greenadexchange.com, 12345, RESELLER\n"), 1..3);
+    /// assert_eq!(lines, AdsTxt::parse_lines("# comment\ngreenadexchange.com, 12345, RESELLER\n\ncontact=adops@example.com"));
+    /// ```
+    pub fn reparse_lines<'a>(lines: &mut Vec<LineData<'a>>, range: Range<usize>, data: &'a str) -> Range<usize> {
+        let start = range.start;
+        let removed = range.len();
+        let len = lines.len();
+        lines.splice(range, data.split('\n').map(|x| LineData::parse(x)));
+        start..start + lines.len() + removed - len
+    }
+}
+
+impl<'a> AdsTxt<'a> {
+    /// Sorts the records and the variables in a documented, deterministic order.
+    ///
+    /// Records are ordered by their [`RecordKey`](crate::RecordKey) (lowercased advertising system domain, then
+    /// account ID, then relation with `DIRECT` before `RESELLER`), then by certification authority ID and
+    /// extension. Variables are grouped by case-insensitive name, then ordered by value. The sort is stable.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let mut adstxt = AdsTxt::parse(
+    ///     "silverssp.com, 9675, RESELLER
+    /// contact=b@example.com
+    /// greenadexchange.com, 12345, RESELLER
+    /// subdomain=divisionone.example.com
+    /// GreenAdExchange.com, 12345, DIRECT
+    /// CONTACT=a@example.com",
+    /// );
+    /// adstxt.sort();
+    /// assert_eq!(
+    ///     adstxt.to_string(),
+    ///     "GreenAdExchange.com, 12345, DIRECT
+    /// greenadexchange.com, 12345, RESELLER
+    /// silverssp.com, 9675, RESELLER
+    /// CONTACT=a@example.com
+    /// contact=b@example.com
+    /// subdomain=divisionone.example.com
+    /// "
+    /// );
+    /// ```
+    pub fn sort(&mut self) {
+        self.records.sort_by_cached_key(|(r, e)| (r.key(), r.authority_id, e.as_ref().map(|x| x.0)));
+        self.variables.sort_by_cached_key(|(v, e)| (v.name.to_ascii_lowercase(), v.value, e.as_ref().map(|x| x.0)));
+    }
+
+    /// Returns the typed values of the well-formed `MANAGERDOMAIN` variables.
+    pub fn manager_domains(&self) -> impl Iterator<Item = ManagerDomain<'a>> + '_ {
+        self.variables.iter().filter_map(|(v, _)| v.manager_domain())
+    }
+
+    /// Returns the `MANAGERDOMAIN` declarations that repeat a country already declared by a preceding one.
+    ///
+    /// The spec allows at most one `MANAGERDOMAIN` per country code, and at most one without a country code.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let adstxt = AdsTxt::parse(
+    ///     "MANAGERDOMAIN=manager.com
+    /// MANAGERDOMAIN=manager.fr, FR
+    /// MANAGERDOMAIN=other.fr, fr",
+    /// );
+    /// assert_eq!(adstxt.duplicate_manager_domains(), vec![ManagerDomain { domain: "other.fr", country: Some("fr") }]);
+    /// ```
+    pub fn duplicate_manager_domains(&self) -> Vec<ManagerDomain<'a>> {
+        let domains: Vec<_> = self.manager_domains().collect();
+        repeated_manager_domains(&domains).into_iter().map(|(i, _)| domains[i].clone()).collect()
+    }
+
+    /// Returns `true` if the file declares no authorized sellers, i.e. it contains at least one record and
+    /// all of its records are the placeholder record.
+    ///
+    /// See also [`Record::is_placeholder`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// assert!(AdsTxt::parse("placeholder.example.com, placeholder, DIRECT, placeholder").is_placeholder_only());
+    /// assert!(!AdsTxt::parse("greenadexchange.com, 12345, DIRECT").is_placeholder_only());
+    /// assert!(!AdsTxt::parse("").is_placeholder_only());
+    /// ```
+    pub fn is_placeholder_only(&self) -> bool {
+        !self.records.is_empty() && self.records.iter().all(|(r, _)| r.is_placeholder())
+    }
+
+    /// Returns the values of the `inventorypartnerdomain` variables.
+    ///
+    /// Variable names are matched case-insensitively.
+    pub fn inventory_partner_domains(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.variables
+            .iter()
+            .filter(|(v, _)| v.name.eq_ignore_ascii_case("inventorypartnerdomain"))
+            .map(|(v, _)| v.value)
+    }
+
+    /// Returns the combined authorization view of this file and the ads.txt files of its inventory partners.
+    ///
+    /// `partners` holds `(partner domain, parsed ads.txt)` pairs fetched by the caller. Only partners declared
+    /// by an `inventorypartnerdomain` variable are taken into account, the others are ignored. Domains are compared
+    /// by their [`canonical_domain`]. Each record is labeled with the [`RecordSource`] it came from.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let publisher = AdsTxt::parse("greenadexchange.com, 12345, DIRECT\ninventorypartnerdomain=partner.com");
+    /// let partner = AdsTxt::parse("blueadexchange.com, XF436, RESELLER");
+    ///
+    /// let partners = [("partner.com", partner)];
+    /// let view = publisher.with_inventory_partners(&partners);
+    /// assert_eq!(view.len(), 2);
+    /// assert_eq!(view[0].1, RecordSource::Own);
+    /// assert_eq!(view[1].0.domain, "blueadexchange.com");
+    /// assert_eq!(view[1].1, RecordSource::Partner("partner.com"));
+    /// ```
+    pub fn with_inventory_partners<'b>(
+        &'b self,
+        partners: &'b [(&'b str, AdsTxt<'b>)],
+    ) -> Vec<(&'b Record<'b>, RecordSource<'b>)> {
+        let mut view: Vec<_> = self.records.iter().map(|(r, _)| (r, RecordSource::Own)).collect();
+        for domain in self.inventory_partner_domains() {
+            for (partner, adstxt) in partners.iter().filter(|(x, _)| canonical_domain(x) == canonical_domain(domain)) {
+                view.extend(adstxt.records.iter().map(|(r, _)| (r, RecordSource::Partner(partner))));
+            }
+        }
+        view
+    }
+}
+
+/// Represents where a record of a combined authorization view came from.
+///
+/// See also [`AdsTxt::with_inventory_partners`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RecordSource<'a> {
+    /// The record is listed in the publisher's own file.
+    Own,
+    /// The record is listed in the file of the inventory partner domain.
+    Partner(&'a str),
 }
 
 #[test]
@@ -139,6 +310,82 @@ subdomain=divisionone.example.com"
     );
 }
 
+#[test]
+fn test_reparse_lines() {
+    let mut lines = AdsTxt::parse_lines("a.com, 1, DIRECT\nb.com, 2, DIRECT\nc.com, 3, DIRECT");
+    assert_eq!(AdsTxt::reparse_lines(&mut lines, 1..2, "# removed"), 1..2);
+    assert_eq!(lines, AdsTxt::parse_lines("a.com, 1, DIRECT\n# removed\nc.com, 3, DIRECT"));
+    assert_eq!(AdsTxt::reparse_lines(&mut lines, 0..3, "x=y"), 0..1);
+    assert_eq!(lines, AdsTxt::parse_lines("x=y"));
+    assert_eq!(AdsTxt::reparse_lines(&mut lines, 1..1, "a.com, 1, DIRECT\nb.com, 2, DIRECT"), 1..3);
+    assert_eq!(lines, AdsTxt::parse_lines("x=y\na.com, 1, DIRECT\nb.com, 2, DIRECT"));
+}
+
+#[test]
+fn test_adstxt_sort() {
+    let mut adstxt = AdsTxt::parse(
+        "b.com, 1, DIRECT
+a.com, 2, DIRECT
+a.com, 10, DIRECT
+a.com, 1, RESELLER, cert
+a.com, 1, RESELLER
+a.com, 1, OTHER
+a.com, 1, DIRECT ; ext
+a.com, 1, DIRECT
+z=1
+a=2
+A=1",
+    );
+    adstxt.sort();
+    assert_eq!(
+        adstxt.to_string(),
+        "a.com, 1, DIRECT
+a.com, 1, DIRECT ; ext
+a.com, 1, RESELLER
+a.com, 1, RESELLER, cert
+a.com, 1, OTHER
+a.com, 10, DIRECT
+a.com, 2, DIRECT
+b.com, 1, DIRECT
+A=1
+a=2
+z=1
+"
+    );
+}
+
+#[test]
+fn test_with_inventory_partners() {
+    let adstxt = AdsTxt::parse(
+        "greenadexchange.com, 12345, DIRECT
+INVENTORYPARTNERDOMAIN=www.partner.com.",
+    );
+    let partner = AdsTxt::parse("blueadexchange.com, XF436, RESELLER");
+    let other = AdsTxt::parse("redssp.com, 57013, RESELLER");
+
+    assert_eq!(adstxt.inventory_partner_domains().collect::<Vec<_>>(), vec!["www.partner.com."]);
+    assert_eq!(adstxt.with_inventory_partners(&[("other.com", other), ("Partner.com", partner)]), vec![
+        (
+            &Record {
+                domain: "greenadexchange.com",
+                account_id: "12345",
+                relation: Relation::Direct,
+                authority_id: None,
+            },
+            RecordSource::Own
+        ),
+        (
+            &Record {
+                domain: "blueadexchange.com",
+                account_id: "XF436",
+                relation: Relation::Reseller,
+                authority_id: None
+            },
+            RecordSource::Partner("Partner.com")
+        ),
+    ]);
+}
+
 #[test]
 fn test_line_data_parse() {
     assert_eq!(LineData::parse(""), LineData::Empty);
@@ -158,13 +405,30 @@ fn test_line_data_parse() {
 
 /// Represents the line data of ads.txt.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value", rename_all = "snake_case"))]
 pub enum LineData<'a> {
     /// Comment.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Comment(Comment<'a>),
     /// Record.
-    Record { record: Record<'a>, extension: Option<Extension<'a>>, comment: Option<Comment<'a>> },
+    Record {
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        record: Record<'a>,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        extension: Option<Extension<'a>>,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        comment: Option<Comment<'a>>,
+    },
     /// Variable record.
-    Variable { variable: Variable<'a>, extension: Option<Extension<'a>>, comment: Option<Comment<'a>> },
+    Variable {
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        variable: Variable<'a>,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        extension: Option<Extension<'a>>,
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        comment: Option<Comment<'a>>,
+    },
     /// Blank line.
     Empty,
     /// Unknown line data.
@@ -173,7 +437,7 @@ pub enum LineData<'a> {
 
 impl LineData<'_> {
     #[inline(always)]
-    fn parse(line: &'_ str) -> LineData<'_> {
+    pub(crate) fn parse(line: &'_ str) -> LineData<'_> {
         match line.trim() {
             "" => LineData::Empty,
             line => {
@@ -193,6 +457,7 @@ impl LineData<'_> {
 
 /// Represents comment of ads.txt.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Comment<'a>(pub &'a str);
 
 #[inline(always)]
@@ -207,41 +472,75 @@ fn test_parse_comment() {
 
 /// Represents record of ads.txt.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Record<'a> {
     /// FIELD #1: Domain name of the advertising system.
     pub domain: &'a str,
     /// FIELD #2: Publisher's Account ID.
     pub account_id: &'a str,
     /// FIELD #3: Type of Account/Relationship.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub relation: Relation<'a>,
     /// FIELD #4: Certification Authority ID.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub authority_id: Option<&'a str>,
 }
 
+impl Record<'_> {
+    /// Returns `true` if the record is the placeholder record defined by the spec for publishers
+    /// that have no authorized sellers:
+    ///
+    /// ```text
+    /// placeholder.example.com, placeholder, DIRECT, placeholder
+    /// ```
+    pub fn is_placeholder(&self) -> bool {
+        self.domain.eq_ignore_ascii_case("placeholder.example.com")
+            && self.account_id == "placeholder"
+            && self.relation == Relation::Direct
+            && self.authority_id == Some("placeholder")
+    }
+}
+
+#[test]
+fn test_record_is_placeholder() {
+    let record =
+        |domain, authority_id| Record { domain, account_id: "placeholder", relation: Relation::Direct, authority_id };
+    assert!(record("placeholder.example.com", Some("placeholder")).is_placeholder());
+    assert!(record("Placeholder.Example.com", Some("placeholder")).is_placeholder());
+    assert!(!record("placeholder.example.com", None).is_placeholder());
+    assert!(!record("greenadexchange.com", Some("placeholder")).is_placeholder());
+}
+
+/// Splits a line into its body, extension data and comment: `body ; extension # comment`.
 #[inline(always)]
-fn parse_record_tail(line_tail: &'_ str) -> Option<(Option<Extension<'_>>, Option<Comment<'_>>)> {
-    let mut iter = line_tail.chars().enumerate();
-    loop {
-        let (i, c) = iter.next()?;
-        match c {
-            ';' => {
-                let start = i + 1;
-                for (j, c) in iter {
-                    if c == '#' {
-                        return Some((
-                            Some(Extension(line_tail[start..j].trim())),
-                            Some(Comment(line_tail[j..].trim())),
-                        ));
-                    }
-                }
-                return Some((Some(Extension(line_tail[start..].trim())), None));
-            }
-            '#' => return Some((None, Some(Comment(line_tail[i..].trim())))),
-            _ => {}
-        }
+fn tokens<'a>(input: &mut &'a str) -> winnow::Result<(&'a str, Option<&'a str>, Option<&'a str>)> {
+    (take_till(0.., [';', '#']), opt(preceded(';', take_till(0.., '#'))), opt(('#', rest).take())).parse_next(input)
+}
+
+#[inline(always)]
+pub(crate) fn split_line(line: &'_ str) -> (&'_ str, Option<Extension<'_>>, Option<Comment<'_>>) {
+    // `tokens` accepts any input.
+    let (body, extension, comment) = tokens.parse(line).unwrap_or((line, None, None));
+    (body, extension.map(|x| Extension(x.trim())), comment.map(|x| Comment(x.trim())))
+}
+
+#[inline(always)]
+pub(crate) fn parse_record_tail(line_tail: &'_ str) -> Option<(Option<Extension<'_>>, Option<Comment<'_>>)> {
+    match split_line(line_tail) {
+        (_, None, None) => None,
+        (_, extension, comment) => Some((extension, comment)),
     }
 }
 
+#[test]
+fn test_split_line() {
+    assert_eq!(split_line(""), ("", None, None));
+    assert_eq!(split_line("a, b, c"), ("a, b, c", None, None));
+    assert_eq!(split_line("a ; b ; c # d # e"), ("a ", Some(Extension("b ; c")), Some(Comment("# d # e"))));
+    assert_eq!(split_line("a # b ; c"), ("a ", None, Some(Comment("# b ; c"))));
+    assert_eq!(split_line("ä ; é # ü"), ("ä ", Some(Extension("é")), Some(Comment("# ü"))));
+}
+
 #[test]
 fn test_parse_record_tail() {
     assert_eq!(parse_record_tail("hoge  "), None);
@@ -257,35 +556,87 @@ fn test_parse_record_tail() {
     assert_eq!(parse_record_tail("# comment  "), Some((None, Some(Comment("# comment")))));
 }
 
+#[inline(always)]
+fn field<'a>(input: &mut &'a str) -> winnow::Result<&'a str> {
+    take_till(0.., ',').parse_next(input)
+}
+
+/// Splits the body of a record line into `domain, account id, relation[, authority id]`.
+#[inline(always)]
+fn record_fields<'a>(input: &mut &'a str) -> winnow::Result<(&'a str, &'a str, &'a str, Option<&'a str>)> {
+    (field, preceded(',', field), preceded(',', field), opt(preceded(',', rest))).parse_next(input)
+}
+
 #[inline(always)]
 fn parse_record(line: &'_ str) -> Option<(Record<'_>, Option<Extension<'_>>, Option<Comment<'_>>)> {
-    let (domain, tail) = line.split_once(',').map(|x| (x.0.trim(), x.1))?;
-    let (account_id, tail) = tail.split_once(',').map(|x| (x.0.trim(), x.1))?;
-    let Some((relation, tail)) = tail.split_once(',').map(|x| (parse_relation(x.0.trim()), x.1)) else {
-        let relation = match tail.split_once([';', '#']) {
-            Some(x) => parse_relation(x.0.trim()),
-            None => parse_relation(tail.trim()),
-        };
-        let authority_id = None;
-
-        return Some(match parse_record_tail(tail) {
-            Some((extension, comment)) => (Record { domain, account_id, relation, authority_id }, extension, comment),
-            None => (Record { domain, account_id, relation, authority_id }, None, None),
-        });
-    };
-    let authority_id = Some(match tail.split_once([';', '#']) {
-        Some(x) => x.0.trim(),
-        None => tail.trim(),
-    });
+    let (body, extension, comment) = split_line(line);
+    let (domain, account_id, relation, authority_id) = record_fields.parse(body).ok()?;
+    // Trailing empty fields such as `DIRECT,` or `DIRECT, ,` are treated as absent, and so is an empty field #4
+    // followed by other fields, which `AdsTxt::diagnose` reports.
+    let authority_id = authority_id
+        .map(|x| x.trim().trim_end_matches(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|x| !x.is_empty() && !x.starts_with(','));
 
-    Some(match parse_record_tail(tail) {
-        Some((extension, comment)) => (Record { domain, account_id, relation, authority_id }, extension, comment),
-        None => (Record { domain, account_id, relation, authority_id }, None, None),
-    })
+    Some((
+        Record {
+            domain: domain.trim(),
+            account_id: account_id.trim(),
+            relation: parse_relation(relation.trim()),
+            authority_id,
+        },
+        extension,
+        comment,
+    ))
 }
 
 #[test]
 fn test_parse_record() {
+    assert_eq!(parse_record("f1, f2"), None);
+    assert_eq!(parse_record("f1, f2 # c1, c2"), None);
+    assert_eq!(
+        parse_record("f1, f2, DIRECT # c1, c2"),
+        Some((
+            Record { domain: "f1", account_id: "f2", relation: Relation::Direct, authority_id: None },
+            None,
+            Some(Comment("# c1, c2"))
+        ))
+    );
+    assert_eq!(
+        parse_record("f1, f2, DIRECT ; e1, e2"),
+        Some((
+            Record { domain: "f1", account_id: "f2", relation: Relation::Direct, authority_id: None },
+            Some(Extension("e1, e2")),
+            None
+        ))
+    );
+    assert_eq!(
+        parse_record("f1, f2, DIRECT,"),
+        Some((Record { domain: "f1", account_id: "f2", relation: Relation::Direct, authority_id: None }, None, None))
+    );
+    assert_eq!(
+        parse_record("f1, f2, DIRECT, , # comment"),
+        Some((
+            Record { domain: "f1", account_id: "f2", relation: Relation::Direct, authority_id: None },
+            None,
+            Some(Comment("# comment"))
+        ))
+    );
+    assert_eq!(
+        parse_record("f1, f2, DIRECT, , f5"),
+        Some((Record { domain: "f1", account_id: "f2", relation: Relation::Direct, authority_id: None }, None, None))
+    );
+    assert_eq!(
+        parse_record("f1, f2, DIRECT, f4,"),
+        Some((
+            Record { domain: "f1", account_id: "f2", relation: Relation::Direct, authority_id: Some("f4") },
+            None,
+            None
+        ))
+    );
+    assert_eq!(
+        parse_record("f1,,DIRECT"),
+        Some((Record { domain: "f1", account_id: "", relation: Relation::Direct, authority_id: None }, None, None))
+    );
     assert_eq!(
         parse_record("f1, f2, f3"),
         Some((
@@ -353,15 +704,42 @@ fn test_parse_record() {
 }
 
 /// Represents type of Account/Relationship.
-#[derive(Debug, PartialEq, Clone)]
+///
+/// With the `serde` feature, a relation is serialized as it is written in ads.txt, e.g. `"DIRECT"`.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
 pub enum Relation<'a> {
     Direct,
     Reseller,
     Unknown(&'a str),
 }
 
+impl<'a> Relation<'a> {
+    /// Returns the relation as it is written in ads.txt.
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Relation::Direct => "DIRECT",
+            Relation::Reseller => "RESELLER",
+            Relation::Unknown(s) => s,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Relation<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> serde::Deserialize<'de> for Relation<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <&'de str>::deserialize(deserializer).map(parse_relation)
+    }
+}
+
 #[inline(always)]
-fn parse_relation(s: &'_ str) -> Relation<'_> {
+pub(crate) fn parse_relation(s: &'_ str) -> Relation<'_> {
     match s {
         "DIRECT" => Relation::Direct,
         "RESELLER" => Relation::Reseller,
@@ -378,28 +756,30 @@ fn test_parse_relation() {
 
 /// Represents variable record.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variable<'a> {
     pub name: &'a str,
     pub value: &'a str,
 }
 
+/// Splits the body of a variable line into `name=value`.
 #[inline(always)]
-fn parse_variable(line: &'_ str) -> Option<(Variable<'_>, Option<Extension<'_>>, Option<Comment<'_>>)> {
-    let (name, tail) = line.split_once('=').map(|x| (x.0.trim(), x.1))?;
+fn variable_fields<'a>(input: &mut &'a str) -> winnow::Result<(&'a str, &'a str)> {
+    (take_till(0.., '='), preceded('=', rest)).parse_next(input)
+}
 
-    let value = match tail.split_once([';', '#']) {
-        Some(x) => x.0.trim(),
-        None => tail.trim(),
-    };
+#[inline(always)]
+fn parse_variable(line: &'_ str) -> Option<(Variable<'_>, Option<Extension<'_>>, Option<Comment<'_>>)> {
+    let (body, extension, comment) = split_line(line);
+    let (name, value) = variable_fields.parse(body).ok()?;
 
-    Some(match parse_record_tail(tail) {
-        Some((extension, comment)) => (Variable { name, value }, extension, comment),
-        None => (Variable { name, value }, None, None),
-    })
+    Some((Variable { name: name.trim(), value: value.trim() }, extension, comment))
 }
 
 #[test]
 fn test_parse_variable() {
+    assert_eq!(parse_variable("name # a=b"), None);
+    assert_eq!(parse_variable("name=a=b"), Some((Variable { name: "name", value: "a=b" }, None, None)));
     assert_eq!(parse_variable("# abc"), None);
     assert_eq!(parse_variable("f1,f2,f3"), None);
     assert_eq!(parse_variable("=abc"), Some((Variable { name: "", value: "abc" }, None, None)));
@@ -417,6 +797,85 @@ fn test_parse_variable() {
     );
 }
 
+impl<'a> Variable<'a> {
+    /// Returns the typed value of a `MANAGERDOMAIN` variable.
+    ///
+    /// The value is a domain optionally followed by an ISO 3166-1 alpha-2 country code, e.g.
+    /// `MANAGERDOMAIN=manager.com, FR`. Returns `None` if the variable is not a `MANAGERDOMAIN` or its value is
+    /// malformed.
+    pub fn manager_domain(&self) -> Option<ManagerDomain<'a>> {
+        if self.name.eq_ignore_ascii_case("managerdomain") {
+            parse_manager_domain(self.value)
+        } else {
+            None
+        }
+    }
+}
+
+/// Represents value of `MANAGERDOMAIN` variable.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManagerDomain<'a> {
+    /// Domain of the manager.
+    pub domain: &'a str,
+    /// ISO 3166-1 alpha-2 country code the declaration applies to. `None` means it applies globally.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub country: Option<&'a str>,
+}
+
+/// Returns the indices of the declarations of `domains` that repeat the country of a preceding one, with the index
+/// of the first declaration of that country.
+pub(crate) fn repeated_manager_domains(domains: &[ManagerDomain<'_>]) -> Vec<(usize, usize)> {
+    let mut seen = Vec::new();
+    domains
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| {
+            let country = x.country.map(|c| c.to_ascii_uppercase());
+            match seen.iter().find(|(c, _)| *c == country) {
+                Some((_, first)) => Some((i, *first)),
+                None => {
+                    seen.push((country, i));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+#[inline(always)]
+fn parse_manager_domain(value: &'_ str) -> Option<ManagerDomain<'_>> {
+    let (domain, country) = match value.split_once(',') {
+        Some((domain, country)) => {
+            let country = country.trim();
+            if country.len() != 2 || !country.bytes().all(|x| x.is_ascii_alphabetic()) {
+                return None;
+            }
+            (domain.trim(), Some(country))
+        }
+        None => (value.trim(), None),
+    };
+    if domain.is_empty() {
+        None
+    } else {
+        Some(ManagerDomain { domain, country })
+    }
+}
+
+#[test]
+fn test_parse_manager_domain() {
+    assert_eq!(parse_manager_domain("manager.com"), Some(ManagerDomain { domain: "manager.com", country: None }));
+    assert_eq!(
+        parse_manager_domain(" manager.com , FR "),
+        Some(ManagerDomain { domain: "manager.com", country: Some("FR") })
+    );
+    assert_eq!(parse_manager_domain("manager.com, FRA"), None);
+    assert_eq!(parse_manager_domain("manager.com,"), None);
+    assert_eq!(parse_manager_domain(", FR"), None);
+    assert_eq!(parse_manager_domain(""), None);
+}
+
 /// Represents extension data of ads.txt record.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Extension<'a>(pub &'a str);
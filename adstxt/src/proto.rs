@@ -0,0 +1,132 @@
+//! [Protocol buffers] messages of parsed ads.txt, encoded with [prost].
+//!
+//! The messages are defined in [`PROTO`], so that services in other languages can exchange them. Convert an
+//! [`AdsTxt`](crate::AdsTxt) with `From`, and encode or decode with [`prost::Message`].
+//!
+//! # Example
+//! ```rust
+//! # use adstxt::*;
+//! use prost::Message;
+//!
+//! let txt = AdsTxt::parse("greenadexchange.com, 12345, DIRECT ; ext\ncontact=adops@example.com");
+//! let bytes = proto::AdsTxt::from(&txt).encode_to_vec();
+//!
+//! let message = proto::AdsTxt::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(AdsTxt::from(&message), txt);
+//! ```
+//!
+//! [Protocol buffers]: https://protobuf.dev/
+//! [prost]: https://docs.rs/prost
+
+use crate::{parser::parse_relation, Extension};
+
+/// The `.proto` definition of the messages of this module.
+pub const PROTO: &str = include_str!("../proto/adstxt.proto");
+
+/// Message of [`crate::AdsTxt`].
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AdsTxt {
+    #[prost(message, repeated, tag = "1")]
+    pub records: Vec<Record>,
+    #[prost(message, repeated, tag = "2")]
+    pub variables: Vec<Variable>,
+}
+
+/// Message of [`crate::Record`] and its extension data.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Record {
+    #[prost(string, tag = "1")]
+    pub domain: String,
+    #[prost(string, tag = "2")]
+    pub account_id: String,
+    #[prost(string, tag = "3")]
+    pub relation: String,
+    #[prost(string, optional, tag = "4")]
+    pub authority_id: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub extension: Option<String>,
+}
+
+/// Message of [`crate::Variable`] and its extension data.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Variable {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub value: String,
+    #[prost(string, optional, tag = "3")]
+    pub extension: Option<String>,
+}
+
+impl From<&crate::AdsTxt<'_>> for AdsTxt {
+    fn from(txt: &crate::AdsTxt<'_>) -> Self {
+        AdsTxt {
+            records: txt
+                .records
+                .iter()
+                .map(|(r, e)| Record {
+                    domain: r.domain.into(),
+                    account_id: r.account_id.into(),
+                    relation: r.relation.as_str().into(),
+                    authority_id: r.authority_id.map(Into::into),
+                    extension: e.as_ref().map(|x| x.0.into()),
+                })
+                .collect(),
+            variables: txt
+                .variables
+                .iter()
+                .map(|(v, e)| Variable {
+                    name: v.name.into(),
+                    value: v.value.into(),
+                    extension: e.as_ref().map(|x| x.0.into()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a AdsTxt> for crate::AdsTxt<'a> {
+    fn from(message: &'a AdsTxt) -> Self {
+        crate::AdsTxt {
+            records: message
+                .records
+                .iter()
+                .map(|x| {
+                    (
+                        crate::Record {
+                            domain: &x.domain,
+                            account_id: &x.account_id,
+                            relation: parse_relation(&x.relation),
+                            authority_id: x.authority_id.as_deref(),
+                        },
+                        x.extension.as_deref().map(Extension),
+                    )
+                })
+                .collect(),
+            variables: message
+                .variables
+                .iter()
+                .map(|x| (crate::Variable { name: &x.name, value: &x.value }, x.extension.as_deref().map(Extension)))
+                .collect(),
+        }
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    use prost::Message;
+
+    let txt = crate::AdsTxt::parse(
+        "greenadexchange.com, 1, DIRECT, d75815a79 ; ext
+blueadexchange.com, 2, RESELLER
+silverssp.com, 3, other
+contact=adops@example.com ; note",
+    );
+    let message = AdsTxt::from(&txt);
+    assert_eq!(message.records[2].relation, "other");
+    assert_eq!(message.variables[0].extension.as_deref(), Some("note"));
+
+    let decoded = AdsTxt::decode(message.encode_to_vec().as_slice()).unwrap();
+    assert_eq!(decoded, message);
+    assert_eq!(crate::AdsTxt::from(&decoded), txt);
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    str,
+    sync::Arc,
+};
+
+use crate::{
+    canonical_domain,
+    interned::{Document, InternedRecord, InternedVariable, Symbol},
+    AdsTxt, AdsTxtCorpus, Record,
+};
+
+const MAGIC: &[u8] = b"ADSTXTSET\x01";
+
+/// Represents parsed ads.txt files keyed by the domain they were fetched from, with the compact storage of
+/// [`AdsTxtCorpus`].
+///
+/// Domains are compared by their [`canonical_domain`]. A set can be saved to and loaded from a compact binary
+/// snapshot with [`AdsTxtSet::write_snapshot`] and [`AdsTxtSet::read_snapshot`].
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// let mut set = AdsTxtSet::new();
+/// set.insert("example.com", &AdsTxt::parse("greenadexchange.com, 12345, DIRECT"));
+/// set.insert("example.org", &AdsTxt::parse("greenadexchange.com, 67890, RESELLER\nsilverssp.com, 9675, DIRECT"));
+///
+/// let authorizations = set.authorizations("GreenAdExchange.com");
+/// assert_eq!(authorizations.iter().map(|(domain, r)| (*domain, r.account_id)).collect::<Vec<_>>(), vec![
+///     ("example.com", "12345"),
+///     ("example.org", "67890")
+/// ]);
+///
+/// let mut snapshot = Vec::new();
+/// set.write_snapshot(&mut snapshot).unwrap();
+/// let loaded = AdsTxtSet::read_snapshot(&snapshot).unwrap();
+/// assert_eq!(loaded.get("www.example.org"), set.get("example.org"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AdsTxtSet {
+    corpus: AdsTxtCorpus,
+    /// Canonical domain to the index of its current document in `corpus`.
+    index: HashMap<String, usize>,
+}
+
+impl AdsTxtSet {
+    /// Returns an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the ads.txt of `domain`, replacing the previous one of the same domain.
+    ///
+    /// Returns `true` if the set did not contain `domain`. The strings of a replaced ads.txt stay in memory until
+    /// the set is written to a snapshot and read back.
+    pub fn insert(&mut self, domain: &str, txt: &AdsTxt<'_>) -> bool {
+        self.corpus.push(domain, txt);
+        self.index.insert(canonical_domain(domain).into_owned(), self.corpus.len() - 1).is_none()
+    }
+
+    /// Returns the ads.txt of `domain`.
+    pub fn get(&self, domain: &str) -> Option<AdsTxt<'_>> {
+        let index = *self.index.get(canonical_domain(domain).as_ref())?;
+        self.corpus.get(index).map(|(_, x)| x)
+    }
+
+    /// Returns `true` if the set contains the ads.txt of `domain`.
+    pub fn contains_domain(&self, domain: &str) -> bool {
+        self.index.contains_key(canonical_domain(domain).as_ref())
+    }
+
+    /// Returns the number of domains.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the set contains no domains.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns an iterator over the domains and their ads.txt, in order of first insertion of the current
+    /// documents.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, AdsTxt<'_>)> + '_ {
+        self.documents().map(|x| self.corpus.document(x))
+    }
+
+    /// Returns the records listing the advertising system `exchange` in any ads.txt of the set, with the domain of
+    /// their ads.txt.
+    ///
+    /// Exchange domains are compared by their [`canonical_domain`], once per distinct string of the set.
+    pub fn authorizations(&self, exchange: &str) -> Vec<(&str, Record<'_>)> {
+        let exchange = canonical_domain(exchange);
+        let matches: Vec<_> = self.corpus.strings.iter().map(|x| canonical_domain(x) == exchange).collect();
+
+        let mut authorizations = Vec::new();
+        for document in self.documents() {
+            let records = self.corpus.records[document.records.clone()].iter();
+            for (record, _) in records.filter(|(r, _)| matches[r.domain.0 as usize]) {
+                authorizations.push((self.corpus.resolve(document.domain), self.corpus.record(record)));
+            }
+        }
+        authorizations
+    }
+
+    /// Writes the set as a binary snapshot.
+    ///
+    /// The snapshot holds each distinct string once, followed by the documents referring to them with
+    /// variable-length integers. It can be loaded back with [`AdsTxtSet::read_snapshot`].
+    pub fn write_snapshot<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        if self.corpus.len() != self.index.len() {
+            // Drop the replaced documents so that their records are not written.
+            return self.iter().collect::<AdsTxtSet>().write_snapshot(writer);
+        }
+
+        let corpus = &self.corpus;
+        let mut buf = Vec::from(MAGIC);
+        write_varint(&mut buf, corpus.strings.len() as u64);
+        for s in &corpus.strings {
+            write_varint(&mut buf, s.len() as u64);
+            buf.extend_from_slice(s.as_bytes());
+        }
+
+        write_varint(&mut buf, corpus.documents.len() as u64);
+        for document in &corpus.documents {
+            write_symbol(&mut buf, document.domain);
+            write_varint(&mut buf, document.records.len() as u64);
+            write_varint(&mut buf, document.variables.len() as u64);
+            for (record, extension) in &corpus.records[document.records.clone()] {
+                write_symbol(&mut buf, record.domain);
+                write_symbol(&mut buf, record.account_id);
+                write_symbol(&mut buf, record.relation);
+                write_optional(&mut buf, record.authority_id);
+                write_optional(&mut buf, *extension);
+            }
+            for (variable, extension) in &corpus.variables[document.variables.clone()] {
+                write_symbol(&mut buf, variable.name);
+                write_symbol(&mut buf, variable.value);
+                write_optional(&mut buf, *extension);
+            }
+        }
+        writer.write_all(&buf)
+    }
+
+    /// Loads a set from a snapshot written by [`AdsTxtSet::write_snapshot`].
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if `data` is not a valid snapshot.
+    pub fn read_snapshot(mut data: &[u8]) -> io::Result<Self> {
+        let buf = &mut data;
+        if !buf.starts_with(MAGIC) {
+            return Err(invalid_data("not an ads.txt set snapshot"));
+        }
+        *buf = &buf[MAGIC.len()..];
+
+        let mut corpus = AdsTxtCorpus::new();
+        for _ in 0..read_varint(buf)? {
+            let len = read_len(buf)?;
+            let s = str::from_utf8(&buf[..len]).map_err(|e| invalid_data(e.to_string()))?;
+            *buf = &buf[len..];
+            let s: Arc<str> = s.into();
+            let symbol = Symbol(u32::try_from(corpus.strings.len()).map_err(|_| invalid_data("too many strings"))?);
+            corpus.strings.push(s.clone());
+            corpus.symbols.insert(s, symbol);
+        }
+
+        let strings = corpus.strings.len();
+        let mut index = HashMap::new();
+        for _ in 0..read_varint(buf)? {
+            let domain = read_symbol(buf, strings)?;
+            let (records, variables) = (read_len(buf)?, read_len(buf)?);
+
+            let start = corpus.records.len();
+            for _ in 0..records {
+                let record = InternedRecord {
+                    domain: read_symbol(buf, strings)?,
+                    account_id: read_symbol(buf, strings)?,
+                    relation: read_symbol(buf, strings)?,
+                    authority_id: read_optional(buf, strings)?,
+                };
+                corpus.records.push((record, read_optional(buf, strings)?));
+            }
+            let records = start..corpus.records.len();
+
+            let start = corpus.variables.len();
+            for _ in 0..variables {
+                let variable = InternedVariable { name: read_symbol(buf, strings)?, value: read_symbol(buf, strings)? };
+                corpus.variables.push((variable, read_optional(buf, strings)?));
+            }
+            let variables = start..corpus.variables.len();
+
+            index.insert(canonical_domain(corpus.resolve(domain)).into_owned(), corpus.documents.len());
+            corpus.documents.push(Document { domain, records, variables });
+        }
+
+        if !buf.is_empty() {
+            return Err(invalid_data("trailing data after snapshot"));
+        }
+        Ok(AdsTxtSet { corpus, index })
+    }
+
+    /// Returns the current document of every domain.
+    fn documents(&self) -> impl Iterator<Item = &Document> + '_ {
+        self.corpus.documents.iter().enumerate().filter_map(|(i, x)| {
+            let domain = canonical_domain(self.corpus.resolve(x.domain));
+            (self.index.get(domain.as_ref()) == Some(&i)).then_some(x)
+        })
+    }
+}
+
+impl<'d, 'a> Extend<(&'d str, AdsTxt<'a>)> for AdsTxtSet {
+    fn extend<T: IntoIterator<Item = (&'d str, AdsTxt<'a>)>>(&mut self, iter: T) {
+        for (domain, txt) in iter {
+            self.insert(domain, &txt);
+        }
+    }
+}
+
+impl<'d, 'a> FromIterator<(&'d str, AdsTxt<'a>)> for AdsTxtSet {
+    fn from_iter<T: IntoIterator<Item = (&'d str, AdsTxt<'a>)>>(iter: T) -> Self {
+        let mut set = AdsTxtSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// Writes an unsigned LEB128 integer.
+#[inline(always)]
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+#[inline(always)]
+fn write_symbol(buf: &mut Vec<u8>, symbol: Symbol) {
+    write_varint(buf, symbol.0 as u64);
+}
+
+/// Writes `0` for `None`, the symbol plus one otherwise.
+#[inline(always)]
+fn write_optional(buf: &mut Vec<u8>, symbol: Option<Symbol>) {
+    write_varint(buf, symbol.map_or(0, |x| x.0 as u64 + 1));
+}
+
+fn read_varint(buf: &mut &[u8]) -> io::Result<u64> {
+    let mut n = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first().ok_or_else(|| invalid_data("unexpected end of snapshot"))?;
+        *buf = rest;
+        n |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Ok(n);
+        }
+    }
+    Err(invalid_data("integer overflow"))
+}
+
+/// Reads a length that must not exceed the remaining data, to reject corrupted input before allocating.
+fn read_len(buf: &mut &[u8]) -> io::Result<usize> {
+    match usize::try_from(read_varint(buf)?) {
+        Ok(n) if n <= buf.len() => Ok(n),
+        _ => Err(invalid_data("length out of bounds")),
+    }
+}
+
+fn read_symbol(buf: &mut &[u8], strings: usize) -> io::Result<Symbol> {
+    match read_varint(buf)? {
+        n if n < strings as u64 => Ok(Symbol(n as u32)),
+        _ => Err(invalid_data("string index out of bounds")),
+    }
+}
+
+fn read_optional(buf: &mut &[u8], strings: usize) -> io::Result<Option<Symbol>> {
+    match read_varint(buf)? {
+        0 => Ok(None),
+        n if n <= strings as u64 => Ok(Some(Symbol((n - 1) as u32))),
+        _ => Err(invalid_data("string index out of bounds")),
+    }
+}
+
+#[inline(always)]
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[test]
+fn test_varint() {
+    for n in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+        let mut buf = Vec::new();
+        write_varint(&mut buf, n);
+        assert_eq!(read_varint(&mut buf.as_slice()).unwrap(), n);
+    }
+    let mut buf = Vec::new();
+    write_varint(&mut buf, 300);
+    assert_eq!(buf, [0xac, 0x02]);
+    assert!(read_varint(&mut &[0x80][..]).is_err());
+    assert!(read_varint(&mut &[0xff; 11][..]).is_err());
+}
+
+#[test]
+fn test_set() {
+    let a = AdsTxt::parse("greenadexchange.com, 1, DIRECT, d75815a79 ; ext\ncontact=x ; y");
+    let b = AdsTxt::parse("GreenAdExchange.com., 2, RESELLER\nsilverssp.com, 3, DIRECT");
+    let mut set: AdsTxtSet = vec![("a.com", a.clone()), ("b.com", a.clone())].into_iter().collect();
+    assert!(!set.insert("WWW.B.COM", &b));
+    assert!(set.insert("c.com", &AdsTxt::parse("")));
+
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.get("a.com"), Some(a.clone()));
+    assert_eq!(set.get("b.com"), Some(b.clone()));
+    assert_eq!(set.get("d.com"), None);
+    assert!(set.contains_domain("c.com."));
+    assert_eq!(set.iter().map(|(x, _)| x).collect::<Vec<_>>(), vec!["a.com", "WWW.B.COM", "c.com"]);
+
+    let authorizations = set.authorizations("greenadexchange.com");
+    assert_eq!(authorizations, vec![("a.com", a.records[0].0.clone()), ("WWW.B.COM", b.records[0].0.clone())]);
+    assert!(set.authorizations("blueadexchange.com").is_empty());
+
+    let mut snapshot = Vec::new();
+    set.write_snapshot(&mut snapshot).unwrap();
+    let loaded = AdsTxtSet::read_snapshot(&snapshot).unwrap();
+    assert_eq!(loaded.iter().collect::<Vec<_>>(), set.iter().collect::<Vec<_>>());
+    assert_eq!(loaded.corpus.len(), 3);
+
+    let mut written = Vec::new();
+    loaded.write_snapshot(&mut written).unwrap();
+    assert_eq!(written, snapshot);
+
+    let mut empty = Vec::new();
+    AdsTxtSet::new().write_snapshot(&mut empty).unwrap();
+    assert!(AdsTxtSet::read_snapshot(&empty).unwrap().is_empty());
+}
+
+#[test]
+fn test_read_snapshot_errors() {
+    let set: AdsTxtSet = vec![("a.com", AdsTxt::parse("greenadexchange.com, 1, DIRECT"))].into_iter().collect();
+    let mut snapshot = Vec::new();
+    set.write_snapshot(&mut snapshot).unwrap();
+
+    for data in [&b""[..], b"ADSTXTSET\x02", &snapshot[..snapshot.len() - 1], &[&snapshot[..], b"\x00"].concat()] {
+        assert_eq!(AdsTxtSet::read_snapshot(data).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    // A record referring to a string out of bounds.
+    let mut corrupted = snapshot.clone();
+    let last = corrupted.len() - 3;
+    corrupted[last] = 100;
+    assert!(AdsTxtSet::read_snapshot(&corrupted).is_err());
+}
@@ -0,0 +1,111 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{AdsTxt, Diagnostic, Spanned, SpannedLineData};
+
+/// Represents where an ads.txt comes from, so that tools can accept documents from any source uniformly.
+///
+/// Implemented for strings and file paths. Sources such as HTTP fetchers implement it by returning the body of
+/// the response.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// fn count_records(source: &impl AdsTxtSource) -> std::io::Result<usize> {
+///     Ok(source.load()?.ads_txt().records.len())
+/// }
+///
+/// assert_eq!(count_records(&"greenadexchange.com, 12345, DIRECT").unwrap(), 1);
+/// assert!(count_records(&std::path::Path::new("/nonexistent/ads.txt")).is_err());
+/// ```
+pub trait AdsTxtSource {
+    /// Loads the document.
+    fn load(&self) -> io::Result<ParsedDocument>;
+}
+
+/// Represents an ads.txt loaded from an [`AdsTxtSource`].
+///
+/// Owns the source text, from which the parse results borrow.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParsedDocument {
+    source: String,
+}
+
+impl ParsedDocument {
+    /// Creates a document from its source text.
+    pub fn new(source: impl Into<String>) -> Self {
+        ParsedDocument { source: source.into() }
+    }
+
+    /// Returns the source text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the records and variables. See [`AdsTxt::parse`].
+    pub fn ads_txt(&self) -> AdsTxt<'_> {
+        AdsTxt::parse(&self.source)
+    }
+
+    /// Returns the line data with byte offsets. See [`AdsTxt::parse_spanned`].
+    pub fn lines(&self) -> Vec<Spanned<SpannedLineData<'_>>> {
+        AdsTxt::parse_spanned(&self.source)
+    }
+
+    /// Returns the syntax problems. See [`AdsTxt::diagnose`].
+    pub fn diagnose(&self) -> Vec<Diagnostic> {
+        AdsTxt::diagnose(&self.source)
+    }
+}
+
+impl AdsTxtSource for str {
+    fn load(&self) -> io::Result<ParsedDocument> {
+        Ok(ParsedDocument::new(self))
+    }
+}
+
+impl AdsTxtSource for String {
+    fn load(&self) -> io::Result<ParsedDocument> {
+        self.as_str().load()
+    }
+}
+
+impl AdsTxtSource for Path {
+    /// Reads the file. Returns an error of kind [`io::ErrorKind::InvalidData`] if it is not valid UTF-8.
+    fn load(&self) -> io::Result<ParsedDocument> {
+        fs::read_to_string(self).map(ParsedDocument::new)
+    }
+}
+
+impl AdsTxtSource for PathBuf {
+    fn load(&self) -> io::Result<ParsedDocument> {
+        self.as_path().load()
+    }
+}
+
+impl<T: AdsTxtSource + ?Sized> AdsTxtSource for &T {
+    fn load(&self) -> io::Result<ParsedDocument> {
+        (**self).load()
+    }
+}
+
+#[test]
+fn test_sources() {
+    let data = "greenadexchange.com, 12345, DIRECT\nunknown";
+    let document = data.load().unwrap();
+    assert_eq!(document.source(), data);
+    assert_eq!(document.ads_txt(), AdsTxt::parse(data));
+    assert_eq!(document.lines(), AdsTxt::parse_spanned(data));
+    assert_eq!(document.diagnose().len(), 1);
+    assert_eq!(data.to_owned().load().unwrap(), document);
+
+    let path = std::env::temp_dir().join(format!("adstxt-test-source-{}.txt", std::process::id()));
+    fs::write(&path, data).unwrap();
+    assert_eq!(path.load().unwrap(), document);
+    assert_eq!(path.as_path().load().unwrap(), document);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(path.load().unwrap_err().kind(), io::ErrorKind::NotFound);
+}
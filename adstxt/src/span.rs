@@ -0,0 +1,200 @@
+use std::ops::Range;
+
+use crate::{AdsTxt, Comment, Extension, LineData, Record, Relation, Variable};
+
+/// Represents a value together with its byte offsets into the parsed source.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+/// Represents the line data of ads.txt where every node carries its byte offsets.
+///
+/// See also [`AdsTxt::parse_spanned`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum SpannedLineData<'a> {
+    /// Comment.
+    Comment(Spanned<Comment<'a>>),
+    /// Record.
+    Record {
+        record: SpannedRecord<'a>,
+        extension: Option<Spanned<Extension<'a>>>,
+        comment: Option<Spanned<Comment<'a>>>,
+    },
+    /// Variable record.
+    Variable {
+        variable: SpannedVariable<'a>,
+        extension: Option<Spanned<Extension<'a>>>,
+        comment: Option<Spanned<Comment<'a>>>,
+    },
+    /// Blank line.
+    Empty,
+    /// Unknown line data.
+    Unknown(Spanned<&'a str>),
+}
+
+/// Represents record of ads.txt where every field carries its byte offsets.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedRecord<'a> {
+    /// FIELD #1: Domain name of the advertising system.
+    pub domain: Spanned<&'a str>,
+    /// FIELD #2: Publisher's Account ID.
+    pub account_id: Spanned<&'a str>,
+    /// FIELD #3: Type of Account/Relationship.
+    pub relation: Spanned<Relation<'a>>,
+    /// FIELD #4: Certification Authority ID.
+    pub authority_id: Option<Spanned<&'a str>>,
+}
+
+impl<'a> SpannedRecord<'a> {
+    /// Returns the record without offsets.
+    pub fn record(&self) -> Record<'a> {
+        Record {
+            domain: self.domain.value,
+            account_id: self.account_id.value,
+            relation: self.relation.value.clone(),
+            authority_id: self.authority_id.as_ref().map(|x| x.value),
+        }
+    }
+}
+
+/// Represents variable record where the name and the value carry their byte offsets.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpannedVariable<'a> {
+    pub name: Spanned<&'a str>,
+    pub value: Spanned<&'a str>,
+}
+
+impl<'a> SpannedVariable<'a> {
+    /// Returns the variable without offsets.
+    pub fn variable(&self) -> Variable<'a> {
+        Variable { name: self.name.value, value: self.value.value }
+    }
+}
+
+impl AdsTxt<'_> {
+    /// Parse the contents of ads.txt, keeping the byte offsets of every node.
+    ///
+    /// The outer [`Spanned`] covers the whole line without the line feed. Offsets are relative to the start of
+    /// `data`. See also [`AdsTxt::parse_lines`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let data = "# comment\ngreenadexchange.com, 12345, DIRECT";
+    /// let lines = AdsTxt::parse_spanned(data);
+    ///
+    /// assert_eq!(lines[1].span, 10..44);
+    /// let SpannedLineData::Record { record, .. } = &lines[1].value else { unreachable!() };
+    /// assert_eq!(record.account_id.span, 31..36);
+    /// assert_eq!(&data[record.relation.span.clone()], "DIRECT");
+    /// ```
+    pub fn parse_spanned(data: &'_ str) -> Vec<Spanned<SpannedLineData<'_>>> {
+        data.split('\n')
+            .map(|line| Spanned { value: SpannedLineData::new(data, LineData::parse(line)), span: offset(data, line) })
+            .collect()
+    }
+}
+
+impl<'a> SpannedLineData<'a> {
+    fn new(data: &'a str, line: LineData<'a>) -> Self {
+        let spanned = |x: &'a str| Spanned { value: x, span: offset(data, x) };
+        let extension = |x: Option<Extension<'a>>| x.map(|x| Spanned { span: offset(data, x.0), value: x });
+        let comment = |x: Option<Comment<'a>>| x.map(|x| Spanned { span: offset(data, x.0), value: x });
+
+        match line {
+            LineData::Comment(x) => SpannedLineData::Comment(Spanned { span: offset(data, x.0), value: x }),
+            LineData::Record { record: r, extension: e, comment: c } => {
+                let account_id = spanned(r.account_id);
+                let relation =
+                    Spanned { span: relation_offset(data, account_id.span.end, &r.relation), value: r.relation };
+                SpannedLineData::Record {
+                    record: SpannedRecord {
+                        domain: spanned(r.domain),
+                        account_id,
+                        relation,
+                        authority_id: r.authority_id.map(spanned),
+                    },
+                    extension: extension(e),
+                    comment: comment(c),
+                }
+            }
+            LineData::Variable { variable: v, extension: e, comment: c } => SpannedLineData::Variable {
+                variable: SpannedVariable { name: spanned(v.name), value: spanned(v.value) },
+                extension: extension(e),
+                comment: comment(c),
+            },
+            LineData::Empty => SpannedLineData::Empty,
+            LineData::Unknown(x) => SpannedLineData::Unknown(spanned(x)),
+        }
+    }
+}
+
+/// Returns the offsets of `part`, which must be a subslice of `data`.
+#[inline(always)]
+pub(crate) fn offset(data: &str, part: &str) -> Range<usize> {
+    let start = part.as_ptr() as usize - data.as_ptr() as usize;
+    start..start + part.len()
+}
+
+/// Returns the offsets of the relation field, which follows the account ID ending at `account_id_end`.
+#[inline(always)]
+fn relation_offset(data: &str, account_id_end: usize, relation: &Relation<'_>) -> Range<usize> {
+    match relation {
+        Relation::Unknown(x) => offset(data, x),
+        x => {
+            let start = account_id_end + data[account_id_end..].find(x.as_str()).unwrap_or_default();
+            start..start + x.as_str().len()
+        }
+    }
+}
+
+#[test]
+fn test_parse_spanned() {
+    let data = "# c\n f1 , f2 , DIRECT , f4 ; ext # comment\nname = value;x\n\nunknown\nf1,f2,RESELLER";
+    let lines = AdsTxt::parse_spanned(data);
+
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[0], Spanned {
+        value: SpannedLineData::Comment(Spanned { value: Comment("# c"), span: 0..3 }),
+        span: 0..3
+    });
+    assert_eq!(lines[1].span, 4..42);
+    assert_eq!(lines[1].value, SpannedLineData::Record {
+        record: SpannedRecord {
+            domain: Spanned { value: "f1", span: 5..7 },
+            account_id: Spanned { value: "f2", span: 10..12 },
+            relation: Spanned { value: Relation::Direct, span: 15..21 },
+            authority_id: Some(Spanned { value: "f4", span: 24..26 }),
+        },
+        extension: Some(Spanned { value: Extension("ext"), span: 29..32 }),
+        comment: Some(Spanned { value: Comment("# comment"), span: 33..42 }),
+    });
+    assert_eq!(lines[2].value, SpannedLineData::Variable {
+        variable: SpannedVariable {
+            name: Spanned { value: "name", span: 43..47 },
+            value: Spanned { value: "value", span: 50..55 }
+        },
+        extension: Some(Spanned { value: Extension("x"), span: 56..57 }),
+        comment: None,
+    });
+    assert_eq!(lines[3], Spanned { value: SpannedLineData::Empty, span: 58..58 });
+    assert_eq!(lines[4].value, SpannedLineData::Unknown(Spanned { value: "unknown", span: 59..66 }));
+    let SpannedLineData::Record { record, .. } = &lines[5].value else { unreachable!() };
+    assert_eq!(record.relation, Spanned { value: Relation::Reseller, span: 73..81 });
+    assert_eq!(record.record(), Record {
+        domain: "f1",
+        account_id: "f2",
+        relation: Relation::Reseller,
+        authority_id: None
+    });
+
+    for line in &lines {
+        if let SpannedLineData::Record { record, .. } = &line.value {
+            assert_eq!(&data[record.domain.span.clone()], record.domain.value);
+            assert_eq!(&data[record.relation.span.clone()], record.relation.value.as_str());
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Helpers loading parsed ads.txt into SQL databases.
+//!
+//! Rows follow [`SCHEMA`]: every record and variable is stored with the domain its ads.txt was fetched from.
+//! Use [`statements`] to get parameterized `INSERT` statements, or [`write_records_tsv`] and
+//! [`write_variables_tsv`] to produce input for bulk loaders such as PostgreSQL's `COPY ... FROM STDIN`.
+
+use std::io::{self, Write};
+
+use crate::AdsTxt;
+
+/// Table definitions of the rows produced by this module.
+pub const SCHEMA: &str = "CREATE TABLE adstxt_records (
+    publisher TEXT NOT NULL,
+    domain TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    relation TEXT NOT NULL,
+    authority_id TEXT,
+    extension TEXT,
+    UNIQUE (publisher, domain, account_id, relation)
+);
+CREATE TABLE adstxt_variables (
+    publisher TEXT NOT NULL,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    extension TEXT,
+    UNIQUE (publisher, name, value)
+);
+";
+
+/// Represents the parameter placeholder syntax of a database.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Placeholder {
+    /// `?`, as used by SQLite and MySQL.
+    #[default]
+    Question,
+    /// `$1`, `$2`, ..., as used by PostgreSQL.
+    Numbered,
+}
+
+/// Represents the options of [`statements`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct StatementOptions {
+    pub placeholder: Placeholder,
+    /// Update the existing row on a unique constraint violation instead of failing, with `ON CONFLICT ... DO
+    /// UPDATE`. Supported by PostgreSQL and SQLite.
+    pub upsert: bool,
+}
+
+/// Represents a parameterized SQL statement. `None` parameters are `NULL`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Statement<'a> {
+    pub sql: String,
+    pub params: Vec<Option<&'a str>>,
+}
+
+/// Returns one `INSERT` statement per record and variable of `(domain, ads.txt)` pairs, records first.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{sql::*, *};
+///
+/// let documents = [("example.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT"))];
+/// let statements = statements(&documents, &StatementOptions::default());
+/// assert_eq!(
+///     statements[0].sql,
+///     "INSERT INTO adstxt_records (publisher, domain, account_id, relation, authority_id, extension) \
+///      VALUES (?, ?, ?, ?, ?, ?)"
+/// );
+/// assert_eq!(statements[0].params, vec![
+///     Some("example.com"),
+///     Some("greenadexchange.com"),
+///     Some("12345"),
+///     Some("DIRECT"),
+///     None,
+///     None,
+/// ]);
+/// ```
+pub fn statements<'a>(documents: &'a [(&'a str, AdsTxt<'a>)], options: &StatementOptions) -> Vec<Statement<'a>> {
+    let record = sql(
+        "adstxt_records",
+        &["publisher", "domain", "account_id", "relation", "authority_id", "extension"],
+        &["publisher", "domain", "account_id", "relation"],
+        options,
+    );
+    let variable =
+        sql("adstxt_variables", &["publisher", "name", "value", "extension"], &["publisher", "name", "value"], options);
+
+    let records = documents.iter().flat_map(|(publisher, txt)| {
+        txt.records.iter().map(|(r, e)| Statement {
+            sql: record.clone(),
+            params: vec![
+                Some(*publisher),
+                Some(r.domain),
+                Some(r.account_id),
+                Some(r.relation.as_str()),
+                r.authority_id,
+                e.as_ref().map(|x| x.0),
+            ],
+        })
+    });
+    let variables = documents.iter().flat_map(|(publisher, txt)| {
+        txt.variables.iter().map(|(v, e)| Statement {
+            sql: variable.clone(),
+            params: vec![Some(*publisher), Some(v.name), Some(v.value), e.as_ref().map(|x| x.0)],
+        })
+    });
+    records.chain(variables).collect()
+}
+
+fn sql(table: &str, columns: &[&str], key: &[&str], options: &StatementOptions) -> String {
+    let placeholders: Vec<_> = (1..=columns.len())
+        .map(|i| match options.placeholder {
+            Placeholder::Question => "?".to_string(),
+            Placeholder::Numbered => format!("${i}"),
+        })
+        .collect();
+    let mut sql = format!("INSERT INTO {table} ({}) VALUES ({})", columns.join(", "), placeholders.join(", "));
+    if options.upsert {
+        let updates: Vec<_> =
+            columns.iter().filter(|x| !key.contains(x)).map(|x| format!("{x} = excluded.{x}")).collect();
+        sql.push_str(&format!(" ON CONFLICT ({}) DO UPDATE SET {}", key.join(", "), updates.join(", ")));
+    }
+    sql
+}
+
+/// Writes the records of `(domain, ads.txt)` pairs as tab-separated rows of the `adstxt_records` table of
+/// [`SCHEMA`].
+///
+/// The output uses the text format of PostgreSQL's `COPY`: `NULL` is written as `\N`, and backslashes, tabs,
+/// line feeds and carriage returns are escaped.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{sql::*, *};
+///
+/// let documents = [("example.com", AdsTxt::parse("greenadexchange.com, 12345, DIRECT ; a\tb"))];
+/// let mut buf = Vec::new();
+/// write_records_tsv(&mut buf, &documents).unwrap();
+/// assert_eq!(buf, b"example.com\tgreenadexchange.com\t12345\tDIRECT\t\\N\ta\\tb\n");
+/// ```
+pub fn write_records_tsv<W: Write>(mut writer: W, documents: &[(&str, AdsTxt<'_>)]) -> io::Result<()> {
+    for (publisher, txt) in documents {
+        for (r, e) in &txt.records {
+            write_row(&mut writer, &[
+                Some(publisher),
+                Some(r.domain),
+                Some(r.account_id),
+                Some(r.relation.as_str()),
+                r.authority_id,
+                e.as_ref().map(|x| x.0),
+            ])?;
+        }
+    }
+    writer.flush()
+}
+
+/// Writes the variables of `(domain, ads.txt)` pairs as tab-separated rows of the `adstxt_variables` table of
+/// [`SCHEMA`], in the same format as [`write_records_tsv`].
+pub fn write_variables_tsv<W: Write>(mut writer: W, documents: &[(&str, AdsTxt<'_>)]) -> io::Result<()> {
+    for (publisher, txt) in documents {
+        for (v, e) in &txt.variables {
+            write_row(&mut writer, &[Some(publisher), Some(v.name), Some(v.value), e.as_ref().map(|x| x.0)])?;
+        }
+    }
+    writer.flush()
+}
+
+fn write_row<W: Write>(writer: &mut W, fields: &[Option<&str>]) -> io::Result<()> {
+    let fields: Vec<_> = fields.iter().map(|x| x.map_or_else(|| "\\N".to_string(), escape)).collect();
+    writeln!(writer, "{}", fields.join("\t"))
+}
+
+#[inline(always)]
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+#[test]
+fn test_statements() {
+    let documents = [
+        ("a.com", AdsTxt::parse("greenadexchange.com, 1, DIRECT, d75815a79 ; ext\ncontact=adops@a.com")),
+        ("b.com", AdsTxt::parse("blueadexchange.com, 2, RESELLER")),
+    ];
+
+    let options = StatementOptions { placeholder: Placeholder::Numbered, upsert: true };
+    let statements = statements(&documents, &options);
+    assert_eq!(statements.iter().map(|x| &x.params).collect::<Vec<_>>(), vec![
+        &vec![Some("a.com"), Some("greenadexchange.com"), Some("1"), Some("DIRECT"), Some("d75815a79"), Some("ext")],
+        &vec![Some("b.com"), Some("blueadexchange.com"), Some("2"), Some("RESELLER"), None, None],
+        &vec![Some("a.com"), Some("contact"), Some("adops@a.com"), None],
+    ]);
+    assert_eq!(
+        statements[0].sql,
+        "INSERT INTO adstxt_records (publisher, domain, account_id, relation, authority_id, extension) \
+         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (publisher, domain, account_id, relation) \
+         DO UPDATE SET authority_id = excluded.authority_id, extension = excluded.extension"
+    );
+    assert_eq!(
+        statements[2].sql,
+        "INSERT INTO adstxt_variables (publisher, name, value, extension) VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (publisher, name, value) DO UPDATE SET extension = excluded.extension"
+    );
+}
+
+#[test]
+fn test_write_tsv() {
+    let documents = [("a.com", AdsTxt::parse("greenadexchange.com, 1, DIRECT, a\\b\ncontact=x\r\nsubdomain=y ; z"))];
+
+    let mut buf = Vec::new();
+    write_records_tsv(&mut buf, &documents).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "a.com\tgreenadexchange.com\t1\tDIRECT\ta\\\\b\t\\N\n");
+
+    let mut buf = Vec::new();
+    write_variables_tsv(&mut buf, &documents).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "a.com\tcontact\tx\t\\N\na.com\tsubdomain\ty\tz\n");
+}
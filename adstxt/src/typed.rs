@@ -0,0 +1,179 @@
+use std::{error, fmt, str::FromStr};
+
+use url::{Host, Url};
+
+use crate::{Record, Variable};
+
+/// Represents a validated domain name, such as FIELD #1 of a record or the value of a `SUBDOMAIN` variable.
+///
+/// The name is stored in its canonical ASCII form: lowercased, internationalized labels converted to punycode,
+/// and without a trailing dot.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::*;
+///
+/// assert_eq!(Domain::parse("GreenAdExchange.com.").unwrap().as_str(), "greenadexchange.com");
+/// assert_eq!(Domain::parse("bücher.example").unwrap().as_str(), "xn--bcher-kva.example");
+/// assert_eq!(Domain::parse("localhost"), Err(DomainError::SingleLabel));
+/// assert!(Domain::parse("green adexchange.com").is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+pub struct Domain(String);
+
+/// Represents an error while parsing [`Domain`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DomainError {
+    /// The value is not a valid host name.
+    Invalid(url::ParseError),
+    /// The value is an IP address.
+    IpAddress,
+    /// The value has a single label, such as `localhost`.
+    SingleLabel,
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainError::Invalid(e) => write!(f, "invalid domain name: {e}"),
+            DomainError::IpAddress => f.write_str("IP address instead of a domain name"),
+            DomainError::SingleLabel => f.write_str("domain name without a top-level domain"),
+        }
+    }
+}
+
+impl error::Error for DomainError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            DomainError::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Domain {
+    /// Parses and canonicalizes `s`. Surrounding whitespace and a trailing dot are ignored.
+    pub fn parse(s: &str) -> Result<Self, DomainError> {
+        match Host::parse(s.trim().trim_end_matches('.')).map_err(DomainError::Invalid)? {
+            Host::Domain(x) if x.contains('.') => Ok(Domain(x)),
+            Host::Domain(_) => Err(DomainError::SingleLabel),
+            Host::Ipv4(_) | Host::Ipv6(_) => Err(DomainError::IpAddress),
+        }
+    }
+
+    /// Returns the canonical ASCII form.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Domain {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Domain::parse(s)
+    }
+}
+
+impl AsRef<str> for Domain {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Record<'_> {
+    /// Returns FIELD #1 as a validated [`Domain`].
+    pub fn domain_name(&self) -> Result<Domain, DomainError> {
+        Domain::parse(self.domain)
+    }
+}
+
+impl Variable<'_> {
+    /// Returns the domain of a domain-valued variable (`OWNERDOMAIN`, `MANAGERDOMAIN`, `SUBDOMAIN` or
+    /// `INVENTORYPARTNERDOMAIN`) as a validated [`Domain`], or `None` for other variables.
+    ///
+    /// The country code of `MANAGERDOMAIN` is not part of the domain.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let variable = Variable { name: "MANAGERDOMAIN", value: "Manager.com, FR" };
+    /// assert_eq!(variable.domain_name(), Some(Domain::parse("manager.com")));
+    /// assert_eq!(Variable { name: "contact", value: "x" }.domain_name(), None);
+    /// ```
+    pub fn domain_name(&self) -> Option<Result<Domain, DomainError>> {
+        let name = self.name.to_ascii_lowercase();
+        match name.as_str() {
+            "ownerdomain" | "subdomain" | "inventorypartnerdomain" => Some(Domain::parse(self.value)),
+            "managerdomain" => Some(Domain::parse(self.value.split(',').next().unwrap_or_default())),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `CONTACT` variable as an `http` or `https` [`Url`], or `None` if the variable is not
+    /// a `CONTACT` or its value is not such a URL (e.g. an email address).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let variable = Variable { name: "CONTACT", value: "https://example.com/contact" };
+    /// assert_eq!(variable.contact_url().unwrap().host_str(), Some("example.com"));
+    /// assert_eq!(Variable { name: "contact", value: "adops@example.com" }.contact_url(), None);
+    /// ```
+    pub fn contact_url(&self) -> Option<Url> {
+        if !self.name.eq_ignore_ascii_case("contact") {
+            return None;
+        }
+        Url::parse(self.value.trim()).ok().filter(|x| matches!(x.scheme(), "http" | "https") && x.host().is_some())
+    }
+}
+
+#[test]
+fn test_domain() {
+    assert_eq!(Domain::parse(" Example.COM. ").unwrap().to_string(), "example.com");
+    assert_eq!("sub.example.co.uk".parse::<Domain>().unwrap().as_str(), "sub.example.co.uk");
+    assert_eq!(Domain::parse("ÉXAMPLE.com").unwrap().as_str(), "xn--xample-9ua.com");
+    assert_eq!(Domain::parse("192.0.2.1"), Err(DomainError::IpAddress));
+    assert_eq!(Domain::parse("[::1]"), Err(DomainError::IpAddress));
+    assert_eq!(Domain::parse("com"), Err(DomainError::SingleLabel));
+    assert_eq!(Domain::parse(""), Err(DomainError::Invalid(url::ParseError::EmptyHost)));
+    assert!(matches!(Domain::parse("https://example.com"), Err(DomainError::Invalid(_))));
+    assert!(matches!(Domain::parse("exa mple.com"), Err(DomainError::Invalid(_))));
+}
+
+#[test]
+fn test_typed_fields() {
+    let txt = crate::AdsTxt::parse(
+        "GreenAdExchange.com, 1, DIRECT
+example, 2, DIRECT
+subdomain=one.example.com
+managerdomain=manager.com, US
+inventorypartnerdomain=bad domain.com
+contact=http://example.com
+contact=ftp://example.com",
+    );
+    assert_eq!(txt.records[0].0.domain_name().unwrap().as_str(), "greenadexchange.com");
+    assert_eq!(txt.records[1].0.domain_name(), Err(DomainError::SingleLabel));
+    assert_eq!(txt.variables.iter().map(|(v, _)| v.domain_name().map(|x| x.is_ok())).collect::<Vec<_>>(), vec![
+        Some(true),
+        Some(true),
+        Some(false),
+        None,
+        None
+    ]);
+    assert_eq!(txt.variables.iter().map(|(v, _)| v.contact_url().map(String::from)).collect::<Vec<_>>(), vec![
+        None,
+        None,
+        None,
+        Some("http://example.com/".to_string()),
+        None
+    ]);
+}
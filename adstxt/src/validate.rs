@@ -0,0 +1,811 @@
+//! Validation rules reporting [`Diagnostic`]s for the output of [`AdsTxt::parse_spanned`].
+//!
+//! Every rule takes the spanned line data so that a document only needs to be parsed once, and the findings
+//! point at the exact field of the source.
+//!
+//! [`AdsTxt::parse_spanned`]: crate::AdsTxt::parse_spanned
+
+use std::{
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+use crate::{
+    canonical_domain, parser::repeated_manager_domains, Diagnostic, Spanned, SpannedLineData, SpannedRecord,
+    SpannedVariable,
+};
+
+/// Represents a set of known advertising system domains.
+///
+/// Domains are compared by their [`canonical_domain`].
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct KnownExchanges {
+    domains: HashSet<String>,
+}
+
+impl KnownExchanges {
+    /// Loads a list with one domain per line. Blank lines and text after `#` are ignored.
+    pub fn from_list(data: &str) -> Self {
+        data.lines().map(|x| x.split('#').next().unwrap_or_default().trim()).filter(|x| !x.is_empty()).collect()
+    }
+
+    /// Returns `true` if `domain` is a known advertising system.
+    pub fn contains(&self, domain: &str) -> bool {
+        self.domains.contains(canonical_domain(domain).as_ref())
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for KnownExchanges {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        KnownExchanges { domains: iter.into_iter().map(|x| canonical_domain(x.as_ref()).into_owned()).collect() }
+    }
+}
+
+/// Returns the records of `lines`.
+fn records<'s, 'a>(lines: &'s [Spanned<SpannedLineData<'a>>]) -> impl Iterator<Item = &'s SpannedRecord<'a>> {
+    lines.iter().filter_map(|x| match &x.value {
+        SpannedLineData::Record { record, .. } => Some(record),
+        _ => None,
+    })
+}
+
+/// Returns the variables of `lines`.
+fn variables<'s, 'a>(lines: &'s [Spanned<SpannedLineData<'a>>]) -> impl Iterator<Item = &'s SpannedVariable<'a>> {
+    lines.iter().filter_map(|x| match &x.value {
+        SpannedLineData::Variable { variable, .. } => Some(variable),
+        _ => None,
+    })
+}
+
+/// Reports records whose FIELD #1 is not a known advertising system.
+///
+/// If `origin` (the domain the file was fetched from) is given, records listing it in FIELD #1 get a dedicated
+/// hint, since putting the publisher's own domain there is the most common mistake.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let known = KnownExchanges::from_list("greenadexchange.com\nblueadexchange.com # comment");
+/// let lines = AdsTxt::parse_spanned("GreenAdExchange.com, 12345, DIRECT\nexample.com, 12345, DIRECT");
+///
+/// let diagnostics = unknown_exchanges(&lines, &known, Some("example.com"));
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].span, 35..46);
+/// ```
+pub fn unknown_exchanges(
+    lines: &[Spanned<SpannedLineData<'_>>],
+    known: &KnownExchanges,
+    origin: Option<&str>,
+) -> Vec<Diagnostic> {
+    records(lines)
+        .filter(|x| !x.domain.value.is_empty() && !known.contains(x.domain.value))
+        .map(|x| {
+            let diagnostic = Diagnostic::warning(
+                x.domain.span.clone(),
+                format!("`{}` is not a known advertising system", x.domain.value),
+            );
+            match origin {
+                Some(origin) if canonical_domain(origin) == canonical_domain(x.domain.value) => diagnostic.with_hint(
+                    "field #1 must be the domain of the advertising system, not the domain of the publisher",
+                ),
+                _ => diagnostic,
+            }
+        })
+        .collect()
+}
+
+/// Represents the status of a certification authority ID in [`TagRegistry`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TagStatus {
+    Active,
+    Expired,
+}
+
+/// Represents a snapshot of the TAG certification authority ID registry.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TagRegistry {
+    ids: HashMap<String, TagStatus>,
+}
+
+/// Represents an error while loading [`TagRegistry`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RegistryError {
+    /// 1-based line number of the snapshot.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for RegistryError {}
+
+impl TagRegistry {
+    /// Loads a CSV snapshot of the registry.
+    ///
+    /// The first line is a header which must contain an `id` (or `tag_id`) column, and may contain a `status`
+    /// column. Rows whose status is `expired` (case-insensitively) are expired, the other rows are active. Fields
+    /// may be wrapped in double quotes but must not contain commas.
+    pub fn from_csv(data: &str) -> Result<Self, RegistryError> {
+        let mut lines = data.lines().enumerate().filter(|(_, x)| !x.trim().is_empty());
+        let header: Vec<_> = match lines.next() {
+            Some((_, x)) => csv_fields(x).map(|x| x.to_ascii_lowercase()).collect(),
+            None => return Ok(TagRegistry::default()),
+        };
+        let Some(id) = header.iter().position(|x| x == "id" || x == "tag_id") else {
+            return Err(RegistryError { line: 1, message: "missing `id` column".into() });
+        };
+        let status = header.iter().position(|x| x == "status");
+
+        let mut ids = HashMap::new();
+        for (i, line) in lines {
+            let fields: Vec<_> = csv_fields(line).collect();
+            let Some(value) = fields.get(id).filter(|x| !x.is_empty()) else {
+                return Err(RegistryError { line: i + 1, message: "missing id".into() });
+            };
+            let expired = status.and_then(|x| fields.get(x)).is_some_and(|x| x.eq_ignore_ascii_case("expired"));
+            ids.insert(value.to_ascii_lowercase(), if expired { TagStatus::Expired } else { TagStatus::Active });
+        }
+        Ok(TagRegistry { ids })
+    }
+
+    /// Returns the status of `id`, or `None` if it is not registered. IDs are compared case-insensitively.
+    pub fn status(&self, id: &str) -> Option<TagStatus> {
+        self.ids.get(&id.to_ascii_lowercase()).copied()
+    }
+}
+
+#[inline(always)]
+fn csv_fields(line: &str) -> impl Iterator<Item = &str> {
+    line.split(',').map(|x| {
+        let x = x.trim();
+        x.strip_prefix('"').and_then(|x| x.strip_suffix('"')).unwrap_or(x)
+    })
+}
+
+/// Reports FIELD #4 values that are unknown to, or expired in, the TAG certification authority registry.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let registry = TagRegistry::from_csv("id,status\nd75815a79,active\nf08c47fec0942fa0,expired").unwrap();
+/// let lines = AdsTxt::parse_spanned(
+///     "greenadexchange.com, 12345, DIRECT, d75815a79
+/// blueadexchange.com, XF436, DIRECT, f08c47fec0942fa0
+/// silverssp.com, 9675, RESELLER, unknown",
+/// );
+///
+/// let diagnostics = certification_authority_ids(&lines, &registry);
+/// assert_eq!(diagnostics.iter().map(|x| x.message.as_str()).collect::<Vec<_>>(), vec![
+///     "certification authority ID `f08c47fec0942fa0` has expired",
+///     "unknown certification authority ID `unknown`",
+/// ]);
+/// ```
+pub fn certification_authority_ids(lines: &[Spanned<SpannedLineData<'_>>], registry: &TagRegistry) -> Vec<Diagnostic> {
+    records(lines)
+        .filter_map(|x| x.authority_id.as_ref())
+        .filter_map(|x| match registry.status(x.value) {
+            Some(TagStatus::Active) => None,
+            Some(TagStatus::Expired) => Some(Diagnostic::warning(
+                x.span.clone(),
+                format!("certification authority ID `{}` has expired", x.value),
+            )),
+            None => {
+                Some(Diagnostic::warning(x.span.clone(), format!("unknown certification authority ID `{}`", x.value)))
+            }
+        })
+        .collect()
+}
+
+/// Reports records whose FIELD #1 is not a known advertising system but looks like one, e.g. `googIe.com`,
+/// `g00gle.com` or `rubiconproject.co`, which are likely typos or spoofing attempts.
+///
+/// A domain is flagged if it reads the same as a known domain once common homoglyphs (`0`/`o`, `1`/`I`/`l`,
+/// `rn`/`m`, `vv`/`w` and Cyrillic look-alikes) are folded, or if it is within a small edit distance of one:
+/// 1 for domains up to 10 characters, 2 above.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let known = KnownExchanges::from_list("google.com\nrubiconproject.com");
+/// let lines = AdsTxt::parse_spanned("googIe.com, 1, DIRECT\nrubiconproject.co, 2, RESELLER\nredssp.com, 3, DIRECT");
+///
+/// let diagnostics = typosquatted_exchanges(&lines, &known);
+/// assert_eq!(diagnostics.iter().map(|x| x.hint.as_deref().unwrap()).collect::<Vec<_>>(), vec![
+///     "did you mean `google.com`?",
+///     "did you mean `rubiconproject.com`?",
+/// ]);
+/// ```
+pub fn typosquatted_exchanges(lines: &[Spanned<SpannedLineData<'_>>], known: &KnownExchanges) -> Vec<Diagnostic> {
+    let mut candidates: Vec<_> = known.domains.iter().map(|x| (x.as_str(), skeleton(x))).collect();
+    candidates.sort_unstable();
+
+    records(lines)
+        .filter(|x| !x.domain.value.is_empty() && !known.contains(x.domain.value))
+        .filter_map(|x| {
+            let domain = canonical_domain(x.domain.value);
+            let skeleton = skeleton(x.domain.value.trim().trim_end_matches('.'));
+            let max_distance = if domain.chars().count() > 10 { 2 } else { 1 };
+            let (similar, _) = candidates
+                .iter()
+                .map(|(k, s)| (*k, if *s == skeleton { 0 } else { edit_distance(&domain, k) }))
+                .filter(|(_, d)| *d <= max_distance)
+                .min_by_key(|(_, d)| *d)?;
+            Some(
+                Diagnostic::warning(
+                    x.domain.span.clone(),
+                    format!("`{}` looks like the known advertising system `{similar}`", x.domain.value),
+                )
+                .with_hint(format!("did you mean `{similar}`?")),
+            )
+        })
+        .collect()
+}
+
+/// Folds homoglyphs so that look-alike domains share the same skeleton.
+fn skeleton(domain: &str) -> String {
+    let folded: String = domain
+        .chars()
+        .map(|c| match c {
+            '0' | 'O' | 'о' | 'О' | 'ο' => 'o',
+            '1' | 'I' | 'l' | 'і' | 'І' | '|' => 'l',
+            'а' | 'А' | 'α' => 'a',
+            'е' | 'Е' => 'e',
+            'с' | 'С' => 'c',
+            'р' | 'Р' => 'p',
+            'х' | 'Х' => 'x',
+            'у' => 'y',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect();
+    folded.replace("rn", "m").replace("vv", "w")
+}
+
+/// Returns the Levenshtein distance between `a` and `b`, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<_> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = (prev + usize::from(ca != *cb)).min(row[j] + 1).min(cur + 1);
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Reports `CONTACT` values that are neither a plausible email address nor a plausible URL.
+///
+/// Email addresses may be prefixed with `mailto:`. URLs must use the `http` or `https` scheme and have a host with
+/// at least two labels. Empty values are reported as errors, other findings as warnings.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let lines = AdsTxt::parse_spanned("contact=adops@example.com\ncontact=https://example.com/ads\ncontact=adops@");
+///
+/// let diagnostics = contacts(&lines);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].message, "`adops@` is not a valid email address");
+/// assert_eq!(diagnostics[0].span, 66..72);
+/// ```
+pub fn contacts(lines: &[Spanned<SpannedLineData<'_>>]) -> Vec<Diagnostic> {
+    variables(lines)
+        .filter(|x| x.name.value.eq_ignore_ascii_case("contact"))
+        .filter_map(|x| {
+            let Spanned { value, span } = &x.value;
+            let message = if value.is_empty() {
+                return Some(
+                    Diagnostic::error(span.clone(), "empty contact").with_hint("expected an email address or a URL"),
+                );
+            } else if value.contains("://") {
+                (!is_url(value)).then(|| format!("`{value}` is not a valid URL"))
+            } else if let Some(email) = strip_prefix_ignore_case(value, "mailto:") {
+                (!is_email(email)).then(|| format!("`{value}` is not a valid email address"))
+            } else if value.contains('@') {
+                (!is_email(value)).then(|| format!("`{value}` is not a valid email address"))
+            } else {
+                Some(format!("`{value}` is neither an email address nor a URL"))
+            }?;
+            Some(Diagnostic::warning(span.clone(), message))
+        })
+        .collect()
+}
+
+#[inline(always)]
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    match s.get(..prefix.len()) {
+        Some(x) if x.eq_ignore_ascii_case(prefix) => Some(&s[prefix.len()..]),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `s` is a domain name with at least two labels.
+fn is_hostname(s: &str) -> bool {
+    let labels: Vec<_> = s.trim_end_matches('.').split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|x| {
+            !x.is_empty()
+                && !x.starts_with('-')
+                && !x.ends_with('-')
+                && x.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !local.chars().any(|c| c.is_whitespace() || c == '@' || c.is_control())
+                && is_hostname(domain)
+        }
+        None => false,
+    }
+}
+
+fn is_url(s: &str) -> bool {
+    let Some(rest) = strip_prefix_ignore_case(s, "https://").or_else(|| strip_prefix_ignore_case(s, "http://")) else {
+        return false;
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|x| x.is_ascii_digit()) => host,
+        _ => authority,
+    };
+    !s.chars().any(char::is_whitespace) && is_hostname(host)
+}
+
+/// Reports `SUBDOMAIN` referrals that are not exactly one level below `origin`, the root domain the file was
+/// fetched from.
+///
+/// Compliant crawlers ignore referrals to unrelated domains and to deeper subdomains, so such entries have no
+/// effect.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let lines = AdsTxt::parse_spanned(
+///     "subdomain=divisionone.example.com
+/// subdomain=a.divisiontwo.example.com
+/// subdomain=example.org",
+/// );
+///
+/// let diagnostics = subdomain_referrals(&lines, "example.com");
+/// assert_eq!(diagnostics.iter().map(|x| x.message.as_str()).collect::<Vec<_>>(), vec![
+///     "`a.divisiontwo.example.com` is more than one level below `example.com`",
+///     "`example.org` is not a subdomain of `example.com`",
+/// ]);
+/// ```
+pub fn subdomain_referrals(lines: &[Spanned<SpannedLineData<'_>>], origin: &str) -> Vec<Diagnostic> {
+    let origin = canonical_domain(origin);
+    variables(lines)
+        .filter(|x| x.name.value.eq_ignore_ascii_case("subdomain"))
+        .filter_map(|x| {
+            let Spanned { value, span } = &x.value;
+            let domain = canonical_domain(value);
+            let message = match domain.strip_suffix(origin.as_ref()).and_then(|x| x.strip_suffix('.')) {
+                _ if domain.is_empty() => "empty subdomain".to_string(),
+                Some(label) if !label.is_empty() && !label.contains('.') => return None,
+                Some(label) if !label.is_empty() => format!("`{value}` is more than one level below `{origin}`"),
+                _ => format!("`{value}` is not a subdomain of `{origin}`"),
+            };
+            Some(
+                Diagnostic::warning(span.clone(), message)
+                    .with_hint("crawlers only follow referrals to subdomains one level below the root domain"),
+            )
+        })
+        .collect()
+}
+
+/// Represents the size limits checked by [`limits`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Limits {
+    /// Maximum length of a line in bytes, without the line feed. Defaults to 1024.
+    pub max_line_length: usize,
+    /// Maximum number of records in a file. Defaults to 10,000, above which many buyers stop processing the file.
+    pub max_records: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { max_line_length: 1024, max_records: 10_000 }
+    }
+}
+
+/// Reports lines longer than [`Limits::max_line_length`], and the first record beyond [`Limits::max_records`].
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let lines = AdsTxt::parse_spanned("greenadexchange.com, 1, DIRECT\nblueadexchange.com, 2, DIRECT");
+///
+/// let diagnostics = limits(&lines, &Limits { max_line_length: 30, max_records: 1 });
+/// assert_eq!(diagnostics.iter().map(|x| x.message.as_str()).collect::<Vec<_>>(), vec![
+///     "file has 2 records, more than the limit of 1",
+/// ]);
+/// ```
+pub fn limits(lines: &[Spanned<SpannedLineData<'_>>], limits: &Limits) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<_> = lines
+        .iter()
+        .filter(|x| x.span.len() > limits.max_line_length)
+        .map(|x| {
+            Diagnostic::warning(
+                x.span.clone(),
+                format!("line is {} bytes long, more than the limit of {}", x.span.len(), limits.max_line_length),
+            )
+        })
+        .collect();
+
+    let mut records = lines.iter().filter(|x| matches!(x.value, SpannedLineData::Record { .. }));
+    if let Some(first) = records.nth(limits.max_records) {
+        let count = limits.max_records + 1 + records.count();
+        diagnostics.push(
+            Diagnostic::warning(
+                first.span.clone(),
+                format!("file has {count} records, more than the limit of {}", limits.max_records),
+            )
+            .with_hint("records beyond the limit may be ignored by buyers"),
+        );
+    }
+    diagnostics.sort_by_key(|x| x.span.start);
+    diagnostics
+}
+
+/// Reports variables repeating a declaration that must be unique: `OWNERDOMAIN` at most once, `MANAGERDOMAIN`
+/// at most once per country code (and once without one), and no repeated `SUBDOMAIN` or `INVENTORYPARTNERDOMAIN`
+/// value.
+///
+/// Each repetition is reported with a hint referring to the line of the first declaration. Malformed
+/// `MANAGERDOMAIN` values are ignored.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let lines = AdsTxt::parse_spanned(
+///     "OWNERDOMAIN=example.com
+/// MANAGERDOMAIN=manager.com, FR
+/// MANAGERDOMAIN=other.com, fr
+/// ownerdomain=example.org",
+/// );
+///
+/// let diagnostics = duplicate_variables(&lines);
+/// assert_eq!(diagnostics.iter().map(|x| (x.message.as_str(), x.hint.as_deref().unwrap())).collect::<Vec<_>>(), vec![
+///     ("more than one `MANAGERDOMAIN` for country `FR`", "first declared on line 2"),
+///     ("more than one `OWNERDOMAIN`", "first declared on line 1"),
+/// ]);
+/// ```
+pub fn duplicate_variables(lines: &[Spanned<SpannedLineData<'_>>]) -> Vec<Diagnostic> {
+    let variables: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| match &x.value {
+            SpannedLineData::Variable { variable, .. } => Some((i + 1, variable)),
+            _ => None,
+        })
+        .collect();
+    let diagnostic = |x: &SpannedVariable<'_>, message: String, first: usize| {
+        Diagnostic::warning(x.name.span.start..x.value.span.end, message)
+            .with_hint(format!("first declared on line {first}"))
+    };
+
+    let (managers, domains): (Vec<_>, Vec<_>) =
+        variables.iter().filter_map(|(line, x)| Some(((*line, *x), x.variable().manager_domain()?))).unzip();
+    let mut diagnostics: Vec<_> = repeated_manager_domains(&domains)
+        .into_iter()
+        .map(|(i, first)| {
+            let message = match domains[i].country {
+                Some(country) => {
+                    format!("more than one `MANAGERDOMAIN` for country `{}`", country.to_ascii_uppercase())
+                }
+                None => "more than one `MANAGERDOMAIN` without a country code".to_string(),
+            };
+            diagnostic(managers[i].1, message, managers[first].0)
+        })
+        .collect();
+
+    let mut seen = HashMap::new();
+    diagnostics.extend(variables.iter().filter_map(|(line, x)| {
+        let name = x.name.value.to_ascii_lowercase();
+        let (key, message) = match name.as_str() {
+            "ownerdomain" => (String::new(), "more than one `OWNERDOMAIN`".to_string()),
+            "subdomain" | "inventorypartnerdomain" => (
+                canonical_domain(x.value.value).into_owned(),
+                format!("duplicate `{}` value `{}`", x.name.value, x.value.value),
+            ),
+            _ => return None,
+        };
+        let first = *seen.entry((name, key)).or_insert(*line);
+        (first != *line).then(|| diagnostic(x, message, first))
+    }));
+    diagnostics.sort_by_key(|x| x.span.start);
+    diagnostics
+}
+
+/// Reports FIELD #1 values and domain-valued variables that are not valid domain names according to
+/// [`Domain::parse`](crate::Domain::parse).
+///
+/// Available with the `url` feature.
+///
+/// # Example
+/// ```rust
+/// # use adstxt::{validate::*, *};
+///
+/// let lines = AdsTxt::parse_spanned("greenadexchange.com, 1, DIRECT\ngreen adexchange.com, 2, DIRECT\nsubdomain=localhost");
+///
+/// let diagnostics = invalid_domains(&lines);
+/// assert_eq!(diagnostics.iter().map(|x| x.span.clone()).collect::<Vec<_>>(), vec![31..51, 73..82]);
+/// ```
+#[cfg(feature = "url")]
+pub fn invalid_domains(lines: &[Spanned<SpannedLineData<'_>>]) -> Vec<Diagnostic> {
+    let records = records(lines)
+        .filter(|x| !x.domain.value.is_empty())
+        .filter_map(|x| Some((&x.domain, x.record().domain_name().err()?)));
+    let variables = variables(lines).filter_map(|x| Some((&x.value, x.variable().domain_name()?.err()?)));
+
+    let mut diagnostics: Vec<_> = records
+        .chain(variables)
+        .map(|(x, e)| {
+            Diagnostic::warning(x.span.clone(), format!("`{}` is not a valid domain name", x.value))
+                .with_hint(e.to_string())
+        })
+        .collect();
+    diagnostics.sort_by_key(|x| x.span.start);
+    diagnostics
+}
+
+#[test]
+fn test_known_exchanges() {
+    let known = KnownExchanges::from_list("# list\nGreenAdExchange.com\n\n  blueadexchange.com.  # trailing dot\n");
+    assert!(known.contains("greenadexchange.com"));
+    assert!(known.contains("BlueAdExchange.com"));
+    assert!(known.contains("blueadexchange.com."));
+    assert!(!known.contains("redssp.com"));
+    assert!(!known.contains("list"));
+    assert_eq!(known, ["greenadexchange.com", "blueadexchange.com"].into_iter().collect());
+}
+
+#[test]
+fn test_unknown_exchanges() {
+    use crate::AdsTxt;
+
+    let known: KnownExchanges = ["greenadexchange.com"].into_iter().collect();
+    let lines = AdsTxt::parse_spanned(
+        "greenadexchange.com, 1, DIRECT\nredssp.com, 2, DIRECT\nExample.com, 3, DIRECT\n, 4, DIRECT",
+    );
+
+    assert_eq!(unknown_exchanges(&lines, &known, Some("example.com")), vec![
+        Diagnostic::warning(31..41, "`redssp.com` is not a known advertising system"),
+        Diagnostic::warning(53..64, "`Example.com` is not a known advertising system")
+            .with_hint("field #1 must be the domain of the advertising system, not the domain of the publisher"),
+    ]);
+    assert_eq!(unknown_exchanges(&lines, &known, None)[1].hint, None);
+    assert_eq!(
+        unknown_exchanges(&lines, &known, Some("www.example.com."))[1].hint,
+        unknown_exchanges(&lines, &known, Some("example.com"))[1].hint
+    );
+}
+
+#[test]
+fn test_tag_registry_from_csv() {
+    let registry = TagRegistry::from_csv("Name,\"TAG_ID\",Status\nA,\"ABC\",Active\n\nB,def,EXPIRED\nC,ghi,").unwrap();
+    assert_eq!(registry.status("abc"), Some(TagStatus::Active));
+    assert_eq!(registry.status("DEF"), Some(TagStatus::Expired));
+    assert_eq!(registry.status("ghi"), Some(TagStatus::Active));
+    assert_eq!(registry.status("jkl"), None);
+
+    assert_eq!(TagRegistry::from_csv("id\nabc").unwrap().status("abc"), Some(TagStatus::Active));
+    assert_eq!(TagRegistry::from_csv(""), Ok(TagRegistry::default()));
+    assert_eq!(
+        TagRegistry::from_csv("name,status\nabc,active"),
+        Err(RegistryError { line: 1, message: "missing `id` column".into() })
+    );
+    assert_eq!(
+        TagRegistry::from_csv("name,id\na,abc\nb"),
+        Err(RegistryError { line: 3, message: "missing id".into() })
+    );
+}
+
+#[test]
+fn test_certification_authority_ids() {
+    use crate::AdsTxt;
+
+    let registry = TagRegistry::from_csv("id,status\nd75815a79,active\nexpired,expired").unwrap();
+    let lines = AdsTxt::parse_spanned(
+        "a.com, 1, DIRECT, D75815A79\nb.com, 2, DIRECT\nc.com, 3, DIRECT, expired\nd.com, 4, DIRECT, x",
+    );
+    assert_eq!(certification_authority_ids(&lines, &registry), vec![
+        Diagnostic::warning(63..70, "certification authority ID `expired` has expired"),
+        Diagnostic::warning(89..90, "unknown certification authority ID `x`"),
+    ]);
+}
+
+#[test]
+fn test_skeleton() {
+    assert_eq!(skeleton("googIe.com"), skeleton("google.com"));
+    assert_eq!(skeleton("g00gle.com"), skeleton("google.com"));
+    assert_eq!(skeleton("rnicrosoft.com"), skeleton("microsoft.com"));
+    assert_eq!(skeleton("gооgle.com"), skeleton("google.com"));
+    assert_ne!(skeleton("goggle.com"), skeleton("google.com"));
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("", ""), 0);
+    assert_eq!(edit_distance("abc", ""), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("rubiconproject.co", "rubiconproject.com"), 1);
+    assert_eq!(edit_distance("аbc", "abc"), 1);
+}
+
+#[test]
+fn test_typosquatted_exchanges() {
+    use crate::AdsTxt;
+
+    let known = KnownExchanges::from_list("google.com\nappnexus.com\npubmatic.com");
+    let lines = AdsTxt::parse_spanned(
+        "google.com, 1, DIRECT
+Google.com, 1, DIRECT
+g00gle.com, 1, DIRECT
+appnexus.co, 1, DIRECT
+www.appnexus.com., 1, DIRECT
+pubrnatic.com, 1, DIRECT
+completelydifferent.com, 1, DIRECT
+go.com, 1, DIRECT",
+    );
+    assert_eq!(
+        typosquatted_exchanges(&lines, &known).into_iter().map(|x| (x.span, x.hint.unwrap())).collect::<Vec<_>>(),
+        vec![
+            (44..54, "did you mean `google.com`?".to_string()),
+            (66..77, "did you mean `appnexus.com`?".to_string()),
+            (118..131, "did you mean `pubmatic.com`?".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_is_email() {
+    assert!(is_email("adops@example.com"));
+    assert!(is_email("first.last+ads@sub.example.co.uk"));
+    assert!(!is_email("adops@"));
+    assert!(!is_email("@example.com"));
+    assert!(!is_email("adops@example"));
+    assert!(!is_email("adops@@example.com"));
+    assert!(!is_email("ad ops@example.com"));
+    assert!(!is_email("adops.example.com"));
+}
+
+#[test]
+fn test_is_url() {
+    assert!(is_url("https://example.com"));
+    assert!(is_url("HTTP://example.com:8080/contact?x=1#form"));
+    assert!(!is_url("ftp://example.com"));
+    assert!(!is_url("https://"));
+    assert!(!is_url("https://localhost/"));
+    assert!(!is_url("https://example..com"));
+    assert!(!is_url("https://example.com/a b"));
+}
+
+#[test]
+fn test_contacts() {
+    use crate::AdsTxt;
+
+    let lines = AdsTxt::parse_spanned(
+        "contact=adops@example.com
+CONTACT=mailto:adops@example.com
+contact=https://example.com/contact
+contact=
+contact=mailto:adops
+contact=https://example
+contact=+1 555 0100
+subdomain=",
+    );
+    assert_eq!(contacts(&lines), vec![
+        Diagnostic::error(103..103, "empty contact").with_hint("expected an email address or a URL"),
+        Diagnostic::warning(112..124, "`mailto:adops` is not a valid email address"),
+        Diagnostic::warning(133..148, "`https://example` is not a valid URL"),
+        Diagnostic::warning(157..168, "`+1 555 0100` is neither an email address nor a URL"),
+    ]);
+}
+
+#[test]
+fn test_subdomain_referrals() {
+    use crate::AdsTxt;
+
+    let lines = AdsTxt::parse_spanned(
+        "subdomain=one.example.com
+SUBDOMAIN=Two.Example.com.
+subdomain=a.b.example.com
+subdomain=example.com
+subdomain=notexample.com
+subdomain=
+contact=a.b.example.com",
+    );
+    let hint = "crawlers only follow referrals to subdomains one level below the root domain";
+    assert_eq!(subdomain_referrals(&lines, "www.example.com"), subdomain_referrals(&lines, "example.com"));
+    assert_eq!(subdomain_referrals(&lines, "Example.com."), vec![
+        Diagnostic::warning(63..78, "`a.b.example.com` is more than one level below `example.com`").with_hint(hint),
+        Diagnostic::warning(89..100, "`example.com` is not a subdomain of `example.com`").with_hint(hint),
+        Diagnostic::warning(111..125, "`notexample.com` is not a subdomain of `example.com`").with_hint(hint),
+        Diagnostic::warning(136..136, "empty subdomain").with_hint(hint),
+    ]);
+}
+
+#[test]
+fn test_limits() {
+    use crate::AdsTxt;
+
+    let lines = AdsTxt::parse_spanned("a.com, 1, DIRECT\n# a longer comment\nb.com, 2, DIRECT\nc.com, 3, DIRECT\nx=y");
+    assert_eq!(limits(&lines, &Limits { max_line_length: 16, max_records: 1 }), vec![
+        Diagnostic::warning(17..35, "line is 18 bytes long, more than the limit of 16"),
+        Diagnostic::warning(36..52, "file has 3 records, more than the limit of 1")
+            .with_hint("records beyond the limit may be ignored by buyers"),
+    ]);
+    assert_eq!(limits(&lines, &Limits { max_line_length: 18, max_records: 3 }), vec![]);
+    assert_eq!(limits(&lines, &Limits::default()), vec![]);
+}
+
+#[test]
+fn test_duplicate_variables() {
+    use crate::AdsTxt;
+
+    let source = "ownerdomain=example.com
+MANAGERDOMAIN=a.com
+MANAGERDOMAIN=b.com, US
+managerdomain=c.com
+MANAGERDOMAIN=d.com, us
+MANAGERDOMAIN=e.com, usa
+subdomain=one.example.com
+inventorypartnerdomain=one.example.com
+SUBDOMAIN=One.Example.com
+inventorypartnerdomain=www.one.example.com.
+OWNERDOMAIN=example.com
+contact=a
+contact=a";
+    let lines = AdsTxt::parse_spanned(source);
+    assert_eq!(AdsTxt::parse(source).duplicate_manager_domains().len(), 2);
+    assert_eq!(duplicate_variables(&lines), vec![
+        Diagnostic::warning(68..87, "more than one `MANAGERDOMAIN` without a country code")
+            .with_hint("first declared on line 2"),
+        Diagnostic::warning(88..111, "more than one `MANAGERDOMAIN` for country `US`")
+            .with_hint("first declared on line 3"),
+        Diagnostic::warning(202..227, "duplicate `SUBDOMAIN` value `One.Example.com`")
+            .with_hint("first declared on line 7"),
+        Diagnostic::warning(228..271, "duplicate `inventorypartnerdomain` value `www.one.example.com.`")
+            .with_hint("first declared on line 8"),
+        Diagnostic::warning(272..295, "more than one `OWNERDOMAIN`").with_hint("first declared on line 1"),
+    ]);
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn test_invalid_domains() {
+    use crate::AdsTxt;
+
+    let lines = AdsTxt::parse_spanned(
+        "exämple.com, 1, DIRECT
+192.0.2.1, 2, DIRECT
+, 3, DIRECT
+ownerdomain=example.com
+managerdomain=bad_/domain.com, US
+contact=not a domain",
+    );
+    assert_eq!(invalid_domains(&lines), vec![
+        Diagnostic::warning(24..33, "`192.0.2.1` is not a valid domain name")
+            .with_hint("IP address instead of a domain name"),
+        Diagnostic::warning(95..114, "`bad_/domain.com, US` is not a valid domain name")
+            .with_hint("invalid domain name: invalid international domain name"),
+    ]);
+}
@@ -0,0 +1,31 @@
+use crate::AdsTxt;
+
+impl AdsTxt<'_> {
+    /// Serializes the parsed contents of ads.txt as YAML.
+    ///
+    /// The document has the same shape as the `serde` serialization used for any other format.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use adstxt::*;
+    ///
+    /// let txt = AdsTxt::parse("greenadexchange.com, 12345, DIRECT, d75815a79 ; ext\ncontact=adops@example.com");
+    /// assert_eq!(
+    ///     txt.to_yaml().unwrap(),
+    ///     "records:
+    /// - - domain: greenadexchange.com
+    ///     account_id: '12345'
+    ///     relation: DIRECT
+    ///     authority_id: d75815a79
+    ///   - ext
+    /// variables:
+    /// - - name: contact
+    ///     value: adops@example.com
+    ///   - null
+    /// "
+    /// );
+    /// ```
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+}
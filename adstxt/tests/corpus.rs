@@ -0,0 +1,123 @@
+use adstxt::*;
+
+/// Edge-case lines shared by every entry point that parses ads.txt.
+fn corpus() -> Vec<(&'static str, LineData<'static>)> {
+    let record = |domain, account_id, relation, authority_id| Record { domain, account_id, relation, authority_id };
+
+    vec![
+        ("", LineData::Empty),
+        ("  \t ", LineData::Empty),
+        ("# comment, with, commas", LineData::Comment(Comment("# comment, with, commas"))),
+        ("  #indented ; comment", LineData::Comment(Comment("#indented ; comment"))),
+        ("example.com, 1, DIRECT", LineData::Record {
+            record: record("example.com", "1", Relation::Direct, None),
+            extension: None,
+            comment: None,
+        }),
+        ("example.com,1,RESELLER,f08c47fec0942fa0", LineData::Record {
+            record: record("example.com", "1", Relation::Reseller, Some("f08c47fec0942fa0")),
+            extension: None,
+            comment: None,
+        }),
+        ("example.com, 1, DIRECT, ", LineData::Record {
+            record: record("example.com", "1", Relation::Direct, None),
+            extension: None,
+            comment: None,
+        }),
+        ("example.com, 1, DIRECT, a, , ", LineData::Record {
+            record: record("example.com", "1", Relation::Direct, Some("a")),
+            extension: None,
+            comment: None,
+        }),
+        ("example.com, 1, direct", LineData::Record {
+            record: record("example.com", "1", Relation::Unknown("direct"), None),
+            extension: None,
+            comment: None,
+        }),
+        ("example.com, 1, DIRECT # owned, operated", LineData::Record {
+            record: record("example.com", "1", Relation::Direct, None),
+            extension: None,
+            comment: Some(Comment("# owned, operated")),
+        }),
+        ("example.com, 1, DIRECT ; ext, data ; more # comment ; not extension", LineData::Record {
+            record: record("example.com", "1", Relation::Direct, None),
+            extension: Some(Extension("ext, data ; more")),
+            comment: Some(Comment("# comment ; not extension")),
+        }),
+        ("exämple.com, ü, DIRECT ; é", LineData::Record {
+            record: record("exämple.com", "ü", Relation::Direct, None),
+            extension: Some(Extension("é")),
+            comment: None,
+        }),
+        ("contact=adops@example.com", LineData::Variable {
+            variable: Variable { name: "contact", value: "adops@example.com" },
+            extension: None,
+            comment: None,
+        }),
+        ("subdomain = a=b # note", LineData::Variable {
+            variable: Variable { name: "subdomain", value: "a=b" },
+            extension: None,
+            comment: Some(Comment("# note")),
+        }),
+        ("example.com, 1", LineData::Unknown("example.com, 1")),
+        ("example.com # a=b", LineData::Unknown("example.com # a=b")),
+        ("example.com 1 DIRECT", LineData::Unknown("example.com 1 DIRECT")),
+    ]
+}
+
+fn unspan(line: SpannedLineData<'_>) -> LineData<'_> {
+    match line {
+        SpannedLineData::Comment(x) => LineData::Comment(x.value),
+        SpannedLineData::Record { record, extension, comment } => LineData::Record {
+            record: record.record(),
+            extension: extension.map(|x| x.value),
+            comment: comment.map(|x| x.value),
+        },
+        SpannedLineData::Variable { variable, extension, comment } => LineData::Variable {
+            variable: variable.variable(),
+            extension: extension.map(|x| x.value),
+            comment: comment.map(|x| x.value),
+        },
+        SpannedLineData::Empty => LineData::Empty,
+        SpannedLineData::Unknown(x) => LineData::Unknown(x.value),
+    }
+}
+
+#[test]
+fn test_corpus_parse_lines() {
+    for (line, expected) in corpus() {
+        assert_eq!(AdsTxt::parse_lines(line), vec![expected], "{line:?}");
+    }
+}
+
+#[test]
+fn test_corpus_parse_spanned() {
+    for (line, expected) in corpus() {
+        let mut lines = AdsTxt::parse_spanned(line);
+        assert_eq!(lines.len(), 1, "{line:?}");
+        let spanned = lines.remove(0);
+        assert_eq!(spanned.span, 0..line.len(), "{line:?}");
+        if let SpannedLineData::Record { record, .. } = &spanned.value {
+            assert_eq!(&line[record.domain.span.clone()], record.domain.value, "{line:?}");
+            assert_eq!(&line[record.account_id.span.clone()], record.account_id.value, "{line:?}");
+            assert_eq!(&line[record.relation.span.clone()], record.relation.value.as_str(), "{line:?}");
+        }
+        assert_eq!(unspan(spanned.value), expected, "{line:?}");
+    }
+}
+
+#[test]
+fn test_corpus_parse_lines_with() {
+    for (line, expected) in corpus() {
+        let (lines, _) = AdsTxt::parse_lines_with(line, &ParseOptions::default());
+        assert_eq!(lines, vec![expected], "{line:?}");
+    }
+}
+
+#[test]
+fn test_corpus_display_round_trip() {
+    for (line, expected) in corpus() {
+        let printed = expected.to_string();
+        assert_eq!(AdsTxt::parse_lines(&printed), vec![expected], "{line:?}");
+    }
+}